@@ -0,0 +1,47 @@
+extern crate grpc;
+extern crate sharedlib;
+
+use grpc::ClientStub;
+use sharedlib::epaxos_grpc::*;
+use sharedlib::logic::{Reconfiguration, ReplicaId};
+use sharedlib::topology::Topology;
+use std::{env, process, sync::Arc};
+
+// admin <topology file> <replica to ask> add <id>
+// admin <topology file> <replica to ask> remove <id>
+//
+// To add a replica, start it with a topology file that lists it with
+// `joining = true`, and ask it to add itself: it catches up first and then
+// asks the members. The topology file given here must list it too.
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() != 5 {
+        eprintln!("Usage: admin <topology file> <replica to ask> add|remove <id>");
+        process::exit(2);
+    }
+    let topology = Topology::load_or_ec2(args.get(1));
+    let asked: u32 = args[2].parse().unwrap();
+    let id: u32 = args[4].parse().unwrap();
+    let reconfig = match args[3].as_str() {
+        "add" => Reconfiguration::Add(
+            topology
+                .get(ReplicaId(id))
+                .expect("id not in topology")
+                .clone(),
+        ),
+        "remove" => Reconfiguration::Remove(ReplicaId(id)),
+        change => panic!("Unknown change {}", change),
+    };
+
+    let replica = topology.get(ReplicaId(asked)).expect("id not in topology");
+    let grpc_client =
+        Arc::new(grpc::Client::new_plain(&replica.host, replica.port, Default::default()).unwrap());
+    let client = EpaxosInternalClient::with_client(grpc_client);
+    match client
+        .reconfigure(grpc::RequestOptions::new(), reconfig.to_grpc())
+        .wait()
+    {
+        Err(e) => panic!("Reconfiguration failed: {}", e),
+        Ok(_) => println!("Done: {:?}", reconfig),
+    }
+}
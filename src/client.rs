@@ -6,15 +6,17 @@ extern crate sharedlib;
 use grpc::ClientStub;
 use rayon::prelude::*;
 use sharedlib::epaxos_grpc::*;
-use sharedlib::logic::{WriteRequest, REPLICA_ADDRESSES, REPLICA_PORT};
+use sharedlib::logic::{ReplicaId, WriteRequest};
+use sharedlib::topology::Topology;
 use std::{env, sync::Arc, time::Instant};
 
 fn main() {
     let args: Vec<String> = env::args().collect();
     let id: u32 = args[1].parse().unwrap();
+    let topology = Topology::load_or_ec2(args.get(2));
     let write_req1 = WriteRequest {
-        key: "pi".to_string(),
-        value: 1,
+        key: b"pi".to_vec(),
+        value: b"1".to_vec(),
     };
     let mut write_reqs = Vec::new();
     write_reqs.push((write_req1.to_grpc(), id));
@@ -22,13 +24,9 @@ fn main() {
         .par_iter_mut()
         .enumerate()
         .for_each(|(i, (req, id))| {
+            let replica = topology.get(ReplicaId(*id)).expect("id not in topology");
             let grpc_client = Arc::new(
-                grpc::Client::new_plain(
-                    REPLICA_ADDRESSES[*id as usize],
-                    REPLICA_PORT,
-                    Default::default(),
-                )
-                .unwrap(),
+                grpc::Client::new_plain(&replica.host, replica.port, Default::default()).unwrap(),
             );
             let client = EpaxosServiceClient::with_client(grpc_client);
             let start = Instant::now();
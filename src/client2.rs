@@ -5,20 +5,25 @@ extern crate sharedlib;
 use grpc::ClientStub;
 use sharedlib::epaxos::*;
 use sharedlib::epaxos_grpc::*;
-use sharedlib::logic::{EU, REPLICA_PORT};
-use std::sync::Arc;
+use sharedlib::logic::ReplicaId;
+use sharedlib::topology::Topology;
+use std::{env, sync::Arc};
 
 fn main() {
+    let args: Vec<String> = env::args().collect();
+    let id: u32 = args[1].parse().unwrap();
+    let topology = Topology::load_or_ec2(args.get(2));
+    let replica = topology.get(ReplicaId(id)).expect("id not in topology");
     let grpc_client =
-        Arc::new(grpc::Client::new_plain(EU, REPLICA_PORT, Default::default()).unwrap());
+        Arc::new(grpc::Client::new_plain(&replica.host, replica.port, Default::default()).unwrap());
     let client = EpaxosServiceClient::with_client(grpc_client);
     let mut write_req = WriteRequest::new();
-    write_req.set_key("pi".to_owned());
-    write_req.set_value(6);
+    write_req.set_key(b"pi".to_vec());
+    write_req.set_value(b"6".to_vec());
     let write_resp = client.write(grpc::RequestOptions::new(), write_req);
     println!("Client2 wrote {:?}", write_resp.wait());
     let mut read_req = ReadRequest::new();
-    read_req.set_key("pi".to_owned());
+    read_req.set_key(b"pi".to_vec());
     let read_resp = client.read(grpc::RequestOptions::new(), read_req);
     match read_resp.wait() {
         Err(e) => panic!("Client2 panic {:?}", e),
@@ -0,0 +1,96 @@
+extern crate grpc;
+extern crate sharedlib;
+
+use grpc::ClientStub;
+use sharedlib::command::{escaped, Command, CommandResult};
+use sharedlib::epaxos_grpc::*;
+use sharedlib::logic::ReplicaId;
+use sharedlib::topology::Topology;
+use std::{env, process, sync::Arc};
+
+// kv <topology file> <replica to ask> put <key> <value>
+// kv <topology file> <replica to ask> delete <key>
+// kv <topology file> <replica to ask> incr <key> <delta>
+// kv <topology file> <replica to ask> append <key> <value>
+// kv <topology file> <replica to ask> cas <key> <expected|-> <value>
+// kv <topology file> <replica to ask> get <key>
+//
+// `-` as the expected value of a CAS expects the key to be unset. Several
+// commands separated by `,` are applied as one, e.g.
+// kv <topology file> <replica to ask> cas a 100 70 , incr b 30
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 5 {
+        eprintln!("Usage: kv <topology file> <replica to ask> <op> <key> [args] [, <op> ...]");
+        process::exit(2);
+    }
+    let topology = Topology::load_or_ec2(args.get(1));
+    let asked: u32 = args[2].parse().unwrap();
+    let mut commands: Vec<Command> = args[3..].split(|arg| arg == ",").map(parse).collect();
+    let command = if commands.len() == 1 {
+        commands.remove(0)
+    } else {
+        Command::Multi(commands)
+    };
+
+    let replica = topology.get(ReplicaId(asked)).expect("id not in topology");
+    let grpc_client =
+        Arc::new(grpc::Client::new_plain(&replica.host, replica.port, Default::default()).unwrap());
+    let client = EpaxosServiceClient::with_client(grpc_client);
+    match client
+        .submit(grpc::RequestOptions::new(), command.to_grpc())
+        .wait()
+    {
+        Err(e) => {
+            eprintln!("{:?} failed: {}", command, e);
+            process::exit(1);
+        }
+        Ok((_, result, _)) => print(&command, &CommandResult::from_grpc(&result)),
+    }
+}
+
+// <op> <key> [args]
+fn parse(args: &[String]) -> Command {
+    let key = args[1].clone().into_bytes();
+    let arg = |i: usize| args.get(i).expect("missing argument").clone();
+    let bytes = |i: usize| arg(i).into_bytes();
+    match args[0].as_str() {
+        "put" => Command::Put {
+            key,
+            value: bytes(2),
+        },
+        "delete" => Command::Delete { key },
+        "incr" => Command::Increment {
+            key,
+            delta: arg(2).parse().unwrap(),
+        },
+        "append" => Command::Append {
+            key,
+            value: bytes(2),
+        },
+        "cas" => Command::Cas {
+            key,
+            expected: Some(bytes(2)).filter(|expected| expected != b"-"),
+            value: bytes(3),
+        },
+        "get" => Command::Get { key },
+        op => panic!("Unknown op {}", op),
+    }
+}
+
+fn print(command: &Command, result: &CommandResult) {
+    match command {
+        Command::Multi(commands) => {
+            println!("ok = {}", result.ok);
+            for (command, result) in commands.iter().zip(result.results.iter()) {
+                print(command, result);
+            }
+        }
+        // Increments return no value, see `Command::Increment`
+        Command::Increment { .. } => println!("ok = {}", result.ok),
+        _ => {
+            let value = result.value.as_deref().map_or("unset".to_string(), escaped);
+            println!("ok = {}, value = {}", result.ok, value);
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use crate::command::{Command, Replicated};
+use crate::logic::*;
+use crate::snapshot::Snapshot;
+use crate::state_machine::StateMachine;
+use std::collections::HashSet;
+
+// What a replica that fell behind asks a peer for
+pub struct CatchUpRequest {
+    pub replica: ReplicaId,
+    // Every slot below executed[r] of replica r is executed...
+    pub executed: Vec<u32>,
+    // ...and these, above the watermarks, are committed already
+    pub committed: Vec<Instance>,
+}
+
+// The committed instances the requester is missing. If the peer dropped
+// some of them from its log, the snapshot stands in for those, and
+// `committed` then starts from what the snapshot covers.
+pub struct CatchUp<C = Command> {
+    pub committed: Vec<Payload<C>>,
+    pub snapshot: Option<Snapshot>,
+}
+
+fn is_committed(state: Option<State>) -> bool {
+    matches!(state, Some(State::Committed) | Some(State::Executed))
+}
+
+impl<C: Replicated> EpaxosLogic<C> {
+    /// Whether we seem to have missed instances that committed elsewhere:
+    /// a row has a hole below an instance we know is committed, a committed
+    /// command depends on something we never heard of, or a peer has
+    /// executed further than us
+    pub fn is_behind(&self) -> bool {
+        for (replica, log) in self.cmds.iter().enumerate() {
+            let last = log
+                .iter()
+                .filter(|(_, log_entry)| log_entry.state >= State::Committed)
+                .map(|(slot, _)| *slot as u32)
+                .max();
+            if let Some(last) = last {
+                let hole = (self.watermarks[replica]..last)
+                    .any(|slot| !is_committed(self.state_of(&Instance::at(replica as u32, slot))));
+                if hole {
+                    return true;
+                }
+            }
+        }
+        let unknown_dep = self
+            .blocking_instances()
+            .iter()
+            .any(|instance| self.state_of(instance).is_none());
+        if unknown_dep {
+            return true;
+        }
+        self.peer_watermarks.values().any(|executed| {
+            executed.iter().enumerate().any(|(replica, watermark)| {
+                let next = Instance::at(replica as u32, self.watermarks[replica]);
+                *watermark > next.slot && !is_committed(self.state_of(&next))
+            })
+        })
+    }
+
+    pub fn catch_up_request(&self) -> CatchUpRequest {
+        let mut committed = Vec::new();
+        for (replica, log) in self.cmds.iter().enumerate() {
+            for (slot, log_entry) in log.iter() {
+                if log_entry.state >= State::Committed && *slot as u32 >= self.watermarks[replica] {
+                    committed.push(Instance::at(replica as u32, *slot as u32));
+                }
+            }
+        }
+        committed.sort_by(sort_instances);
+        CatchUpRequest {
+            replica: self.id,
+            executed: self.watermarks.clone(),
+            committed,
+        }
+    }
+
+    /// Answers a peer that fell behind. `state` must hold exactly the
+    /// instances we have executed so far, in case we have to send a snapshot.
+    pub fn catch_up<S: StateMachine>(&self, request: &CatchUpRequest, state: &S) -> CatchUp<C> {
+        let mut from = request.executed.clone();
+        from.resize(self.cmds.len(), 0);
+        let too_old = from
+            .iter()
+            .zip(self.truncated.iter())
+            .any(|(from, truncated)| from < truncated);
+        let snapshot = if too_old {
+            // Everything we still hold goes along with the snapshot, so the
+            // requester has no holes between it and the rest of the log
+            from = self.truncated.clone();
+            Some(self.snapshot(state))
+        } else {
+            None
+        };
+        let held: HashSet<&Instance> = request.committed.iter().collect();
+        let mut committed = Vec::new();
+        for (replica, log) in self.cmds.iter().enumerate() {
+            for (slot, log_entry) in log.iter() {
+                let instance = Instance::at(replica as u32, *slot as u32);
+                if log_entry.state >= State::Committed
+                    && instance.slot >= from[replica]
+                    && !held.contains(&instance)
+                {
+                    committed.push(Payload::from_log_entry(log_entry, instance));
+                }
+            }
+        }
+        committed.sort_by(|a, b| sort_instances(&a.instance, &b.instance));
+        println!(
+            "Replica {} is catching up on {} instances{}",
+            request.replica.0,
+            committed.len(),
+            if too_old { " and a snapshot" } else { "" }
+        );
+        CatchUp {
+            committed,
+            snapshot,
+        }
+    }
+
+    /// Whether we can replace our state with the snapshot. Whatever we
+    /// executed beyond it has to be applied again, so it must still be in
+    /// our log.
+    pub fn can_restore(&self, snapshot: &Snapshot) -> bool {
+        self.truncated
+            .iter()
+            .enumerate()
+            .all(|(replica, truncated)| {
+                snapshot.watermarks.get(replica).cloned().unwrap_or(0) >= *truncated
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_machine::KvStore;
+    use crate::topology::Topology;
+
+    fn logic() -> EpaxosLogic {
+        EpaxosLogic::init(ReplicaId(0), &Topology::ec2())
+    }
+
+    fn put(logic: &mut EpaxosLogic, instance: Instance, deps: Vec<Instance>, state: State) {
+        let log_entry = LogEntry {
+            command: Command::Put {
+                key: format!("key{}", instance.slot).into_bytes(),
+                value: b"1".to_vec(),
+            },
+            seq: 1,
+            deps,
+            state,
+            noop: false,
+            ballot: Ballot::default(),
+            epoch: 0,
+            reconfig: None,
+        };
+        logic.update_log(log_entry, &instance);
+    }
+
+    fn commit(logic: &mut EpaxosLogic, instance: Instance) {
+        put(logic, instance, vec![], State::Committed);
+    }
+
+    fn instances(catch_up: &CatchUp) -> Vec<Instance> {
+        catch_up
+            .committed
+            .iter()
+            .map(|payload| payload.instance)
+            .collect()
+    }
+
+    #[test]
+    fn hole_in_a_row_means_we_are_behind() {
+        let mut logic = logic();
+        commit(&mut logic, Instance::at(1, 0));
+        commit(&mut logic, Instance::at(1, 2));
+        assert!(logic.is_behind());
+        commit(&mut logic, Instance::at(1, 1));
+        assert!(!logic.is_behind());
+    }
+
+    #[test]
+    fn unknown_dependency_means_we_are_behind() {
+        let mut logic = logic();
+        let dep = Instance::at(2, 5);
+        put(&mut logic, Instance::at(1, 0), vec![dep], State::Committed);
+        assert!(logic.is_behind());
+        // One we know of but that has not committed is for recovery
+        put(&mut logic, dep, vec![], State::PreAccepted);
+        assert!(!logic.is_behind());
+    }
+
+    #[test]
+    fn catch_up_sends_only_what_the_requester_misses() {
+        let mut peer = logic();
+        let (a, b, c) = (Instance::at(0, 0), Instance::at(0, 1), Instance::at(1, 0));
+        for instance in [a, b, c].iter() {
+            commit(&mut peer, *instance);
+        }
+        let request = CatchUpRequest {
+            replica: ReplicaId(1),
+            executed: vec![1],
+            committed: vec![c],
+        };
+        let catch_up = peer.catch_up(&request, &KvStore::default());
+        assert_eq!(instances(&catch_up), vec![b]);
+        assert!(catch_up.snapshot.is_none());
+    }
+
+    #[test]
+    fn requester_below_the_truncation_point_gets_a_snapshot() {
+        let mut peer = logic();
+        for slot in 0..3 {
+            commit(&mut peer, Instance::at(0, slot));
+        }
+        peer.execute();
+        commit(&mut peer, Instance::at(0, 3));
+        peer.truncate(&[2, 0, 0, 0, 0]).unwrap();
+        let mut requester = logic();
+        let request = requester.catch_up_request();
+        let catch_up = peer.catch_up(&request, &KvStore::default());
+        // Everything from the truncation point on, even what the snapshot
+        // covers, so the requester has no holes
+        assert_eq!(
+            instances(&catch_up),
+            vec![Instance::at(0, 2), Instance::at(0, 3)]
+        );
+        let snapshot = catch_up.snapshot.unwrap();
+        assert_eq!(snapshot.watermarks, vec![3, 0, 0, 0, 0]);
+        assert_eq!(snapshot.truncated, vec![2, 0, 0, 0, 0]);
+        assert!(requester.can_restore(&snapshot));
+        // A requester that dropped more than the snapshot covers cannot
+        // apply what lies in between again
+        requester.truncated = vec![4, 0, 0, 0, 0];
+        assert!(!requester.can_restore(&snapshot));
+    }
+}
@@ -0,0 +1,272 @@
+use std::fmt;
+
+// Keys and values are opaque bytes
+pub type Key = Vec<u8>;
+pub type Value = Vec<u8>;
+
+// What an instance agrees on, unless it is a no-op or a reconfiguration
+#[derive(Clone, PartialEq)]
+pub enum Command {
+    Put {
+        key: Key,
+        value: Value,
+    },
+    Delete {
+        key: Key,
+    },
+    // Adds `delta` to the key read as an ASCII decimal integer, 0 if unset.
+    // It wraps around on overflow, so that increments commute. For the same
+    // reason, its result carries no value.
+    Increment {
+        key: Key,
+        delta: i64,
+    },
+    // Appends to the key, empty if unset
+    Append {
+        key: Key,
+        value: Value,
+    },
+    // Sets the key to `value` if it holds `expected`. None expects it unset.
+    Cas {
+        key: Key,
+        expected: Option<Value>,
+        value: Value,
+    },
+    // A linearizable read, see `ReadRequest`
+    Get {
+        key: Key,
+    },
+    // Applies the commands in order, as one. If any of them has no effect,
+    // none of them has.
+    Multi(Vec<Command>),
+}
+
+// What a command did, sent back to the client once it executed
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CommandResult {
+    // False if the command had no effect: a CAS that found another value,
+    // an Increment of a value that is not an integer, or a Delete of an unset
+    // key
+    pub ok: bool,
+    // The value of the key after the command, None if unset, and always for
+    // an Increment
+    pub value: Option<Value>,
+    // For a Multi, the result of each of its commands
+    pub results: Vec<CommandResult>,
+}
+
+// How a command touches a key
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Access {
+    Read,
+    // Adds to an integer, see `Command::Increment`
+    Add,
+    Write,
+}
+
+impl Access {
+    // Reads commute with reads, and adds with adds
+    pub fn commutes(self, other: Access) -> bool {
+        self == other && self != Access::Write
+    }
+
+    // A Multi that touches a key in two ways, e.g. reads it and adds to it,
+    // commutes with neither
+    fn merge(self, other: Access) -> Access {
+        if self == other {
+            self
+        } else {
+            Access::Write
+        }
+    }
+}
+
+/// Which commands have to be ordered. Replicas may execute commands that do
+/// not interfere in different orders, so they do not depend on each other
+/// and commit on the fast path even when they are concurrent.
+pub trait Interference {
+    /// The keys the command touches. Commands without a key in common never
+    /// interfere.
+    fn keys(&self) -> Vec<&[u8]>;
+
+    /// Whether the command interferes with every command on `key`. A later
+    /// command on the key then depends on it rather than on earlier ones.
+    fn is_barrier(&self, key: &[u8]) -> bool;
+
+    /// Whether the order of the two commands matters. It must be symmetric.
+    fn interferes(&self, other: &Self) -> bool;
+}
+
+impl Interference for Command {
+    fn keys(&self) -> Vec<&[u8]> {
+        self.accesses().into_iter().map(|(key, _)| key).collect()
+    }
+
+    fn is_barrier(&self, key: &[u8]) -> bool {
+        self.accesses()
+            .iter()
+            .any(|(touched, access)| *touched == key && *access == Access::Write)
+    }
+
+    fn interferes(&self, other: &Command) -> bool {
+        let theirs = other.accesses();
+        self.accesses().into_iter().any(|(key, access)| {
+            theirs
+                .binary_search_by(|(touched, _)| touched.cmp(&key))
+                .is_ok_and(|i| !access.commutes(theirs[i].1))
+        })
+    }
+}
+
+/// What the replicas agree on: a command that the log orders by how it
+/// interferes with others, and that it sends and stores as bytes. The
+/// default command stands in for that of no-ops and reconfigurations.
+pub trait Replicated: Interference + Clone + Default + fmt::Debug + Send + 'static {
+    fn encode(&self) -> Vec<u8>;
+
+    /// Decodes what `encode` returned. Replicas are not Byzantine and the
+    /// write-ahead log is checksummed, so `bytes` always come from it.
+    fn decode(bytes: &[u8]) -> Self;
+}
+
+// Stands in for the command of no-ops and reconfigurations, which have none
+impl Default for Command {
+    fn default() -> Self {
+        Command::Put {
+            key: Key::new(),
+            value: Value::new(),
+        }
+    }
+}
+
+impl Command {
+    // Empty for a Multi, see `accesses`
+    pub fn key(&self) -> &[u8] {
+        match self {
+            Command::Put { key, .. }
+            | Command::Delete { key }
+            | Command::Increment { key, .. }
+            | Command::Append { key, .. }
+            | Command::Cas { key, .. }
+            | Command::Get { key } => key,
+            Command::Multi(_) => &[],
+        }
+    }
+
+    /// The keys the command touches, once each and sorted, and how
+    pub fn accesses(&self) -> Vec<(&[u8], Access)> {
+        let mut accesses = match self {
+            Command::Multi(commands) => commands.iter().flat_map(Command::accesses).collect(),
+            Command::Get { key } => vec![(key.as_slice(), Access::Read)],
+            Command::Increment { key, .. } => vec![(key.as_slice(), Access::Add)],
+            command => vec![(command.key(), Access::Write)],
+        };
+        accesses.sort_by_key(|(key, _)| *key);
+        accesses.dedup_by(|later, kept| {
+            let same = later.0 == kept.0;
+            if same {
+                kept.1 = kept.1.merge(later.1);
+            }
+            same
+        });
+        accesses
+    }
+}
+
+// Bytes as text where they are printable, escaped otherwise
+pub fn escaped(bytes: &[u8]) -> String {
+    bytes.escape_ascii().to_string()
+}
+
+impl fmt::Debug for Command {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Command::Put { key, value } => {
+                write!(f, "Put({} = {})", escaped(key), escaped(value))
+            }
+            Command::Delete { key } => write!(f, "Delete({})", escaped(key)),
+            Command::Increment { key, delta } => {
+                write!(f, "Increment({} by {})", escaped(key), delta)
+            }
+            Command::Append { key, value } => {
+                write!(f, "Append({} += {})", escaped(key), escaped(value))
+            }
+            Command::Cas {
+                key,
+                expected,
+                value,
+            } => write!(
+                f,
+                "Cas({}: {} -> {})",
+                escaped(key),
+                expected.as_deref().map_or("unset".to_string(), escaped),
+                escaped(value)
+            ),
+            Command::Get { key } => write!(f, "Get({})", escaped(key)),
+            Command::Multi(commands) => write!(f, "Multi{:?}", commands),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get(key: &[u8]) -> Command {
+        Command::Get { key: key.to_vec() }
+    }
+
+    fn incr(key: &[u8]) -> Command {
+        Command::Increment {
+            key: key.to_vec(),
+            delta: 1,
+        }
+    }
+
+    fn put(key: &[u8]) -> Command {
+        Command::Put {
+            key: key.to_vec(),
+            value: b"1".to_vec(),
+        }
+    }
+
+    #[test]
+    fn reads_commute() {
+        assert!(!get(b"k").interferes(&get(b"k")));
+        assert!(!get(b"k").is_barrier(b"k"));
+    }
+
+    #[test]
+    fn increments_commute() {
+        assert!(!incr(b"k").interferes(&incr(b"k")));
+        assert!(!incr(b"k").is_barrier(b"k"));
+    }
+
+    #[test]
+    fn reads_and_increments_interfere() {
+        assert!(get(b"k").interferes(&incr(b"k")));
+        assert!(incr(b"k").interferes(&get(b"k")));
+    }
+
+    #[test]
+    fn writes_interfere_on_their_key_only() {
+        assert!(put(b"k").is_barrier(b"k"));
+        assert!(put(b"k").interferes(&get(b"k")));
+        assert!(incr(b"k").interferes(&put(b"k")));
+        assert!(!put(b"k").interferes(&put(b"j")));
+    }
+
+    #[test]
+    fn multi_that_reads_and_adds_a_key_writes_it() {
+        let multi = Command::Multi(vec![get(b"k"), incr(b"k"), incr(b"j")]);
+        assert_eq!(
+            multi.accesses(),
+            vec![(b"j".as_ref(), Access::Add), (b"k".as_ref(), Access::Write)]
+        );
+        assert!(multi.is_barrier(b"k"));
+        assert!(!multi.is_barrier(b"j"));
+        assert!(multi.interferes(&get(b"k")));
+        assert!(multi.interferes(&incr(b"k")));
+        assert!(!multi.interferes(&incr(b"j")));
+    }
+}
@@ -1,18 +1,26 @@
+use crate::catch_up::{CatchUp, CatchUpRequest};
+use crate::command::{Command, CommandResult, Replicated};
 use crate::epaxos as grpc;
 use crate::logic::*;
+use crate::snapshot::{Snapshot, Watermarks};
+use crate::state_machine::KvStore;
+use crate::topology::{ReplicaConfig, Topology};
+use crate::wal::Record;
+use protobuf::Message;
+use std::collections::HashMap;
 
 impl WriteRequest {
     pub fn from_grpc(req: &grpc::WriteRequest) -> Self {
         WriteRequest {
             key: req.get_key().to_owned(),
-            value: req.get_value(),
+            value: req.get_value().to_owned(),
         }
     }
 
     pub fn to_grpc(&self) -> grpc::WriteRequest {
         let mut req = grpc::WriteRequest::new();
         req.set_key(self.key.to_owned());
-        req.set_value(self.value);
+        req.set_value(self.value.to_owned());
         req
     }
 }
@@ -33,64 +41,248 @@ impl ReadRequest {
     pub fn from_grpc(req: &grpc::ReadRequest) -> Self {
         ReadRequest {
             key: req.get_key().to_owned(),
+            linearizable: req.get_linearizable(),
         }
     }
 
     pub fn to_grpc(&self) -> grpc::ReadRequest {
         let mut req = grpc::ReadRequest::new();
         req.set_key(self.key.to_owned());
+        req.set_linearizable(self.linearizable);
         req
     }
 }
 
 impl ReadResponse {
     pub fn from_grpc(res: &grpc::ReadResponse) -> Self {
-        ReadResponse { value: res.value }
+        ReadResponse {
+            value: if res.get_found() {
+                Some(res.get_value().to_owned())
+            } else {
+                None
+            },
+        }
     }
 
     pub fn to_grpc(&self) -> grpc::ReadResponse {
         let mut res = grpc::ReadResponse::new();
-        res.set_value(self.value);
+        if let Some(value) = &self.value {
+            res.set_value(value.to_owned());
+            res.set_found(true);
+        }
         res
     }
 }
 
-impl Payload {
+impl Command {
+    pub fn from_grpc(command: &grpc::Command) -> Self {
+        let key = command.get_key().to_owned();
+        let value = command.get_value().to_owned();
+        match command.get_op() {
+            grpc::Command_Op::PUT => Command::Put { key, value },
+            grpc::Command_Op::DELETE => Command::Delete { key },
+            grpc::Command_Op::INCREMENT => Command::Increment {
+                key,
+                delta: command.get_delta(),
+            },
+            grpc::Command_Op::APPEND => Command::Append { key, value },
+            grpc::Command_Op::CAS => Command::Cas {
+                key,
+                expected: if command.get_expected_found() {
+                    Some(command.get_expected().to_owned())
+                } else {
+                    None
+                },
+                value,
+            },
+            grpc::Command_Op::GET => Command::Get { key },
+            grpc::Command_Op::MULTI => Command::Multi(
+                command
+                    .get_commands()
+                    .iter()
+                    .map(Command::from_grpc)
+                    .collect(),
+            ),
+        }
+    }
+
+    pub fn to_grpc(&self) -> grpc::Command {
+        let mut command = grpc::Command::new();
+        command.set_key(self.key().to_owned());
+        match self {
+            Command::Put { value, .. } => {
+                command.set_op(grpc::Command_Op::PUT);
+                command.set_value(value.to_owned());
+            }
+            Command::Delete { .. } => command.set_op(grpc::Command_Op::DELETE),
+            Command::Increment { delta, .. } => {
+                command.set_op(grpc::Command_Op::INCREMENT);
+                command.set_delta(*delta);
+            }
+            Command::Append { value, .. } => {
+                command.set_op(grpc::Command_Op::APPEND);
+                command.set_value(value.to_owned());
+            }
+            Command::Cas {
+                expected, value, ..
+            } => {
+                command.set_op(grpc::Command_Op::CAS);
+                if let Some(expected) = expected {
+                    command.set_expected_found(true);
+                    command.set_expected(expected.to_owned());
+                }
+                command.set_value(value.to_owned());
+            }
+            Command::Get { .. } => command.set_op(grpc::Command_Op::GET),
+            Command::Multi(commands) => {
+                command.set_op(grpc::Command_Op::MULTI);
+                command.set_commands(protobuf::RepeatedField::from_vec(
+                    commands.iter().map(Command::to_grpc).collect(),
+                ));
+            }
+        }
+        command
+    }
+}
+
+impl CommandResult {
+    pub fn from_grpc(result: &grpc::CommandResult) -> Self {
+        CommandResult {
+            ok: result.get_ok(),
+            value: if result.get_found() {
+                Some(result.get_value().to_owned())
+            } else {
+                None
+            },
+            results: result
+                .get_results()
+                .iter()
+                .map(CommandResult::from_grpc)
+                .collect(),
+        }
+    }
+
+    pub fn to_grpc(&self) -> grpc::CommandResult {
+        let mut result = grpc::CommandResult::new();
+        result.set_ok(self.ok);
+        if let Some(value) = &self.value {
+            result.set_found(true);
+            result.set_value(value.to_owned());
+        }
+        result.set_results(protobuf::RepeatedField::from_vec(
+            self.results.iter().map(CommandResult::to_grpc).collect(),
+        ));
+        result
+    }
+}
+
+impl EpaxosError {
+    pub fn status(&self) -> ::grpc::GrpcStatus {
+        match self {
+            EpaxosError::Timeout(_) => ::grpc::GrpcStatus::DeadlineExceeded,
+            EpaxosError::NoQuorum(_) => ::grpc::GrpcStatus::Unavailable,
+            EpaxosError::Preempted => ::grpc::GrpcStatus::Aborted,
+            EpaxosError::NotMember => ::grpc::GrpcStatus::FailedPrecondition,
+            EpaxosError::CommandTooLarge { .. } => ::grpc::GrpcStatus::Argument,
+        }
+    }
+
+    pub fn to_grpc(&self) -> ::grpc::Error {
+        ::grpc::Error::GrpcMessage(::grpc::GrpcMessageError {
+            grpc_status: self.status() as i32,
+            grpc_message: self.to_string(),
+        })
+    }
+}
+
+impl Replicated for Command {
+    fn encode(&self) -> Vec<u8> {
+        self.to_grpc().write_to_bytes().unwrap()
+    }
+
+    fn decode(bytes: &[u8]) -> Self {
+        Command::from_grpc(&protobuf::parse_from_bytes(bytes).unwrap())
+    }
+}
+
+impl<C: Replicated> Payload<C> {
     pub fn from_grpc(payload: &grpc::Payload) -> Self {
         Payload {
-            write_req: WriteRequest::from_grpc(payload.get_write_req()),
+            command: C::decode(payload.get_command()),
             seq: payload.get_seq(),
             deps: payload.get_deps().iter().map(Instance::from_grpc).collect(),
             instance: Instance::from_grpc(payload.get_instance()),
+            noop: payload.get_noop(),
+            ballot: Ballot::from_grpc(payload.get_ballot()),
+            reconfig: if payload.has_reconfig() {
+                Some(Reconfiguration::from_grpc(payload.get_reconfig()))
+            } else {
+                None
+            },
         }
     }
 
     pub fn to_grpc(&self) -> grpc::Payload {
         //println!("[Payyload to grpc] self = {:?}", self);
         let mut payload = grpc::Payload::new();
-        payload.set_write_req(self.write_req.to_grpc());
+        payload.set_command(self.command.encode());
         payload.set_seq(self.seq);
         payload.set_deps(protobuf::RepeatedField::from_vec(
             self.deps.iter().map(|dep| dep.to_grpc()).collect(),
         ));
         payload.set_instance(Instance::to_grpc(&self.instance));
+        payload.set_noop(self.noop);
+        payload.set_ballot(self.ballot.to_grpc());
+        if let Some(reconfig) = &self.reconfig {
+            payload.set_reconfig(reconfig.to_grpc());
+        }
         //println!("[Payyload to grpc] converted payload = {:?}", payload);
         payload
     }
 }
 
-impl AcceptOKPayload {
+impl<C: Replicated> PreAcceptOKPayload<C> {
+    pub fn from_grpc(payload: &grpc::PreAcceptOKPayload) -> Self {
+        PreAcceptOKPayload {
+            ok: payload.get_ok(),
+            ballot: Ballot::from_grpc(payload.get_ballot()),
+            payload: Payload::from_grpc(payload.get_payload()),
+        }
+    }
+
+    pub fn to_grpc(&self) -> grpc::PreAcceptOKPayload {
+        let mut payload = grpc::PreAcceptOKPayload::new();
+        payload.set_ok(self.ok);
+        payload.set_ballot(self.ballot.to_grpc());
+        payload.set_payload(self.payload.to_grpc());
+        payload
+    }
+}
+
+impl<C: Replicated> AcceptOKPayload<C> {
     pub fn from_grpc(payload: &grpc::AcceptOKPayload) -> Self {
         AcceptOKPayload {
-            write_req: WriteRequest::from_grpc(payload.get_command()),
+            ok: payload.get_ok(),
+            ballot: Ballot::from_grpc(payload.get_ballot()),
+            command: C::decode(payload.get_command()),
             instance: Instance::from_grpc(payload.get_instance()),
+            committed: if payload.has_committed() {
+                Some(Payload::from_grpc(payload.get_committed()))
+            } else {
+                None
+            },
         }
     }
 
     pub fn to_grpc(&self) -> grpc::AcceptOKPayload {
         let mut payload = grpc::AcceptOKPayload::new();
-        payload.set_command(self.write_req.to_grpc());
+        payload.set_ok(self.ok);
+        payload.set_ballot(self.ballot.to_grpc());
+        payload.set_command(self.command.encode());
         payload.set_instance(self.instance.to_grpc());
+        if let Some(committed) = &self.committed {
+            payload.set_committed(committed.to_grpc());
+        }
         payload
     }
 }
@@ -100,6 +292,7 @@ impl Instance {
         Instance {
             replica: instance.get_replica(),
             slot: instance.get_slot(),
+            epoch: instance.get_epoch(),
         }
     }
 
@@ -108,7 +301,300 @@ impl Instance {
         let mut instance = grpc::Instance::new();
         instance.set_replica(self.replica);
         instance.set_slot(self.slot);
+        instance.set_epoch(self.epoch);
         // println!("[instance] converted instance = {:?}", instance);
         instance
     }
 }
+
+impl Reconfiguration {
+    pub fn from_grpc(reconfig: &grpc::Reconfiguration) -> Self {
+        let replica = ReplicaConfig::from_grpc(reconfig.get_replica());
+        match reconfig.get_change() {
+            grpc::MembershipChange::ADD => Reconfiguration::Add(replica),
+            grpc::MembershipChange::REMOVE => Reconfiguration::Remove(ReplicaId(replica.id)),
+        }
+    }
+
+    pub fn to_grpc(&self) -> grpc::Reconfiguration {
+        let mut reconfig = grpc::Reconfiguration::new();
+        match self {
+            Reconfiguration::Add(replica) => {
+                reconfig.set_change(grpc::MembershipChange::ADD);
+                reconfig.set_replica(replica.to_grpc());
+            }
+            Reconfiguration::Remove(id) => {
+                reconfig.set_change(grpc::MembershipChange::REMOVE);
+                let mut replica = grpc::ReplicaInfo::new();
+                replica.set_id(id.0);
+                reconfig.set_replica(replica);
+            }
+        }
+        reconfig
+    }
+}
+
+impl ReplicaConfig {
+    pub fn from_grpc(replica: &grpc::ReplicaInfo) -> Self {
+        ReplicaConfig {
+            id: replica.get_id(),
+            host: replica.get_host().to_owned(),
+            port: replica.get_port() as u16,
+            region: replica.get_region().to_owned(),
+            quorum: replica.get_quorum().to_vec(),
+            joining: false,
+        }
+    }
+
+    pub fn to_grpc(&self) -> grpc::ReplicaInfo {
+        let mut replica = grpc::ReplicaInfo::new();
+        replica.set_id(self.id);
+        replica.set_host(self.host.clone());
+        replica.set_port(self.port as u32);
+        replica.set_region(self.region.clone());
+        replica.set_quorum(self.quorum.clone());
+        replica
+    }
+}
+
+impl Ballot {
+    pub fn from_grpc(ballot: &grpc::Ballot) -> Self {
+        Ballot {
+            epoch: ballot.get_epoch(),
+            number: ballot.get_number(),
+            replica: ballot.get_replica(),
+        }
+    }
+
+    pub fn to_grpc(&self) -> grpc::Ballot {
+        let mut ballot = grpc::Ballot::new();
+        ballot.set_epoch(self.epoch);
+        ballot.set_number(self.number);
+        ballot.set_replica(self.replica);
+        ballot
+    }
+}
+
+impl State {
+    pub fn from_grpc(state: grpc::State) -> Option<Self> {
+        match state {
+            grpc::State::NONE => None,
+            grpc::State::PRE_ACCEPTED => Some(State::PreAccepted),
+            grpc::State::ACCEPTED => Some(State::Accepted),
+            grpc::State::COMMITTED => Some(State::Committed),
+        }
+    }
+
+    pub fn to_grpc(&self) -> grpc::State {
+        match self {
+            State::PreAccepted => grpc::State::PRE_ACCEPTED,
+            State::Accepted => grpc::State::ACCEPTED,
+            // Execution is local, other replicas only care that it committed
+            State::Committed | State::Executed => grpc::State::COMMITTED,
+        }
+    }
+}
+
+impl PreparePayload {
+    pub fn from_grpc(payload: &grpc::PreparePayload) -> Self {
+        PreparePayload {
+            ballot: Ballot::from_grpc(payload.get_ballot()),
+            instance: Instance::from_grpc(payload.get_instance()),
+        }
+    }
+
+    pub fn to_grpc(&self) -> grpc::PreparePayload {
+        let mut payload = grpc::PreparePayload::new();
+        payload.set_ballot(self.ballot.to_grpc());
+        payload.set_instance(self.instance.to_grpc());
+        payload
+    }
+}
+
+impl<C: Replicated> PrepareOKPayload<C> {
+    pub fn from_grpc(payload: &grpc::PrepareOKPayload) -> Self {
+        PrepareOKPayload {
+            ok: payload.get_ok(),
+            ballot: Ballot::from_grpc(payload.get_ballot()),
+            replica: ReplicaId(payload.get_replica()),
+            instance: Instance::from_grpc(payload.get_instance()),
+            entry: State::from_grpc(payload.get_state())
+                .map(|state| (state, Payload::from_grpc(payload.get_payload()))),
+        }
+    }
+
+    pub fn to_grpc(&self) -> grpc::PrepareOKPayload {
+        let mut payload = grpc::PrepareOKPayload::new();
+        payload.set_ok(self.ok);
+        payload.set_ballot(self.ballot.to_grpc());
+        payload.set_replica(self.replica.0);
+        payload.set_instance(self.instance.to_grpc());
+        if let Some((state, entry)) = &self.entry {
+            payload.set_state(state.to_grpc());
+            payload.set_payload(entry.to_grpc());
+        }
+        payload
+    }
+}
+
+impl<C: Replicated> Record<C> {
+    pub fn from_grpc(record: &grpc::WalRecord) -> Self {
+        match State::from_grpc(record.get_state()) {
+            Some(state) => {
+                let payload = Payload::from_grpc(record.get_payload());
+                Record::Entry(payload.instance, LogEntry::from_payload(&payload, state))
+            }
+            None => Record::Promise(PreparePayload::from_grpc(record.get_promise())),
+        }
+    }
+
+    pub fn to_grpc(&self) -> grpc::WalRecord {
+        let mut record = grpc::WalRecord::new();
+        match self {
+            Record::Entry(instance, log_entry) => {
+                record.set_state(log_entry.state.to_grpc());
+                record.set_payload(Payload::from_log_entry(log_entry, *instance).to_grpc());
+            }
+            Record::Promise(promise) => record.set_promise(promise.to_grpc()),
+        }
+        record
+    }
+}
+
+impl Watermarks {
+    pub fn from_grpc(watermarks: &grpc::Watermarks) -> Self {
+        Watermarks {
+            replica: ReplicaId(watermarks.get_replica()),
+            executed: watermarks.get_executed().to_vec(),
+        }
+    }
+
+    pub fn to_grpc(&self) -> grpc::Watermarks {
+        let mut watermarks = grpc::Watermarks::new();
+        watermarks.set_replica(self.replica.0);
+        watermarks.set_executed(self.executed.clone());
+        watermarks
+    }
+}
+
+impl KvStore {
+    pub fn from_grpc(store: &grpc::KvStore) -> Self {
+        KvStore {
+            map: store
+                .get_entries()
+                .iter()
+                .map(|entry| (entry.get_key().to_owned(), entry.get_value().to_owned()))
+                .collect::<HashMap<_, _>>(),
+        }
+    }
+
+    pub fn to_grpc(&self) -> grpc::KvStore {
+        let mut store = grpc::KvStore::new();
+        store.set_entries(protobuf::RepeatedField::from_vec(
+            self.map
+                .iter()
+                .map(|(key, value)| {
+                    let mut entry = grpc::KeyValue::new();
+                    entry.set_key(key.clone());
+                    entry.set_value(value.clone());
+                    entry
+                })
+                .collect(),
+        ));
+        store
+    }
+}
+
+impl Snapshot {
+    pub fn from_grpc(snapshot: &grpc::Snapshot) -> Self {
+        Snapshot {
+            state: snapshot.get_state().to_vec(),
+            watermarks: snapshot.get_watermarks().to_vec(),
+            executed: snapshot
+                .get_executed()
+                .iter()
+                .map(Instance::from_grpc)
+                .collect(),
+            truncated: snapshot.get_truncated().to_vec(),
+            epoch: snapshot.get_epoch(),
+            members: Topology {
+                replicas: snapshot
+                    .get_members()
+                    .iter()
+                    .map(ReplicaConfig::from_grpc)
+                    .collect(),
+            },
+        }
+    }
+
+    pub fn to_grpc(&self) -> grpc::Snapshot {
+        let mut snapshot = grpc::Snapshot::new();
+        snapshot.set_state(self.state.clone());
+        snapshot.set_watermarks(self.watermarks.clone());
+        snapshot.set_executed(protobuf::RepeatedField::from_vec(
+            self.executed.iter().map(Instance::to_grpc).collect(),
+        ));
+        snapshot.set_truncated(self.truncated.clone());
+        snapshot.set_epoch(self.epoch);
+        snapshot.set_members(protobuf::RepeatedField::from_vec(
+            self.members
+                .replicas
+                .iter()
+                .map(ReplicaConfig::to_grpc)
+                .collect(),
+        ));
+        snapshot
+    }
+}
+
+impl CatchUpRequest {
+    pub fn from_grpc(request: &grpc::CatchUpRequest) -> Self {
+        CatchUpRequest {
+            replica: ReplicaId(request.get_replica()),
+            executed: request.get_executed().to_vec(),
+            committed: request
+                .get_committed()
+                .iter()
+                .map(Instance::from_grpc)
+                .collect(),
+        }
+    }
+
+    pub fn to_grpc(&self) -> grpc::CatchUpRequest {
+        let mut request = grpc::CatchUpRequest::new();
+        request.set_replica(self.replica.0);
+        request.set_executed(self.executed.clone());
+        request.set_committed(protobuf::RepeatedField::from_vec(
+            self.committed.iter().map(Instance::to_grpc).collect(),
+        ));
+        request
+    }
+}
+
+impl<C: Replicated> CatchUp<C> {
+    pub fn from_grpc(reply: &grpc::CatchUpReply) -> Self {
+        CatchUp {
+            committed: reply
+                .get_committed()
+                .iter()
+                .map(Payload::from_grpc)
+                .collect(),
+            snapshot: if reply.has_snapshot() {
+                Some(Snapshot::from_grpc(reply.get_snapshot()))
+            } else {
+                None
+            },
+        }
+    }
+
+    pub fn to_grpc(&self) -> grpc::CatchUpReply {
+        let mut reply = grpc::CatchUpReply::new();
+        reply.set_committed(protobuf::RepeatedField::from_vec(
+            self.committed.iter().map(Payload::to_grpc).collect(),
+        ));
+        if let Some(snapshot) = &self.snapshot {
+            reply.set_snapshot(snapshot.to_grpc());
+        }
+        reply
+    }
+}
@@ -29,8 +29,8 @@ const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_2_8_1;
 #[derive(PartialEq,Clone,Default)]
 pub struct WriteRequest {
     // message fields
-    pub key: ::std::string::String,
-    pub value: i32,
+    pub key: ::std::vec::Vec<u8>,
+    pub value: ::std::vec::Vec<u8>,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
@@ -47,10 +47,10 @@ impl WriteRequest {
         ::std::default::Default::default()
     }
 
-    // string key = 1;
+    // bytes key = 1;
 
 
-    pub fn get_key(&self) -> &str {
+    pub fn get_key(&self) -> &[u8] {
         &self.key
     }
     pub fn clear_key(&mut self) {
@@ -58,35 +58,46 @@ impl WriteRequest {
     }
 
     // Param is passed by value, moved
-    pub fn set_key(&mut self, v: ::std::string::String) {
+    pub fn set_key(&mut self, v: ::std::vec::Vec<u8>) {
         self.key = v;
     }
 
     // Mutable pointer to the field.
     // If field is not initialized, it is initialized with default value first.
-    pub fn mut_key(&mut self) -> &mut ::std::string::String {
+    pub fn mut_key(&mut self) -> &mut ::std::vec::Vec<u8> {
         &mut self.key
     }
 
     // Take field
-    pub fn take_key(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.key, ::std::string::String::new())
+    pub fn take_key(&mut self) -> ::std::vec::Vec<u8> {
+        ::std::mem::replace(&mut self.key, ::std::vec::Vec::new())
     }
 
-    // int32 value = 2;
+    // bytes value = 2;
 
 
-    pub fn get_value(&self) -> i32 {
-        self.value
+    pub fn get_value(&self) -> &[u8] {
+        &self.value
     }
     pub fn clear_value(&mut self) {
-        self.value = 0;
+        self.value.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_value(&mut self, v: i32) {
+    pub fn set_value(&mut self, v: ::std::vec::Vec<u8>) {
         self.value = v;
     }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_value(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.value
+    }
+
+    // Take field
+    pub fn take_value(&mut self) -> ::std::vec::Vec<u8> {
+        ::std::mem::replace(&mut self.value, ::std::vec::Vec::new())
+    }
 }
 
 impl ::protobuf::Message for WriteRequest {
@@ -99,14 +110,10 @@ impl ::protobuf::Message for WriteRequest {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.key)?;
+                    ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.key)?;
                 },
                 2 => {
-                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
-                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    }
-                    let tmp = is.read_int32()?;
-                    self.value = tmp;
+                    ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.value)?;
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -121,10 +128,10 @@ impl ::protobuf::Message for WriteRequest {
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
         if !self.key.is_empty() {
-            my_size += ::protobuf::rt::string_size(1, &self.key);
+            my_size += ::protobuf::rt::bytes_size(1, &self.key);
         }
-        if self.value != 0 {
-            my_size += ::protobuf::rt::value_size(2, self.value, ::protobuf::wire_format::WireTypeVarint);
+        if !self.value.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(2, &self.value);
         }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
@@ -133,10 +140,10 @@ impl ::protobuf::Message for WriteRequest {
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
         if !self.key.is_empty() {
-            os.write_string(1, &self.key)?;
+            os.write_bytes(1, &self.key)?;
         }
-        if self.value != 0 {
-            os.write_int32(2, self.value)?;
+        if !self.value.is_empty() {
+            os.write_bytes(2, &self.value)?;
         }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
@@ -180,12 +187,12 @@ impl ::protobuf::Message for WriteRequest {
         unsafe {
             descriptor.get(|| {
                 let mut fields = ::std::vec::Vec::new();
-                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
                     "key",
                     |m: &WriteRequest| { &m.key },
                     |m: &mut WriteRequest| { &mut m.key },
                 ));
-                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt32>(
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
                     "value",
                     |m: &WriteRequest| { &m.value },
                     |m: &mut WriteRequest| { &mut m.value },
@@ -213,7 +220,7 @@ impl ::protobuf::Message for WriteRequest {
 impl ::protobuf::Clear for WriteRequest {
     fn clear(&mut self) {
         self.key.clear();
-        self.value = 0;
+        self.value.clear();
         self.unknown_fields.clear();
     }
 }
@@ -395,7 +402,8 @@ impl ::protobuf::reflect::ProtobufValue for WriteResponse {
 #[derive(PartialEq,Clone,Default)]
 pub struct ReadRequest {
     // message fields
-    pub key: ::std::string::String,
+    pub key: ::std::vec::Vec<u8>,
+    pub linearizable: bool,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
@@ -412,10 +420,10 @@ impl ReadRequest {
         ::std::default::Default::default()
     }
 
-    // string key = 1;
+    // bytes key = 1;
 
 
-    pub fn get_key(&self) -> &str {
+    pub fn get_key(&self) -> &[u8] {
         &self.key
     }
     pub fn clear_key(&mut self) {
@@ -423,19 +431,34 @@ impl ReadRequest {
     }
 
     // Param is passed by value, moved
-    pub fn set_key(&mut self, v: ::std::string::String) {
+    pub fn set_key(&mut self, v: ::std::vec::Vec<u8>) {
         self.key = v;
     }
 
     // Mutable pointer to the field.
     // If field is not initialized, it is initialized with default value first.
-    pub fn mut_key(&mut self) -> &mut ::std::string::String {
+    pub fn mut_key(&mut self) -> &mut ::std::vec::Vec<u8> {
         &mut self.key
     }
 
     // Take field
-    pub fn take_key(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.key, ::std::string::String::new())
+    pub fn take_key(&mut self) -> ::std::vec::Vec<u8> {
+        ::std::mem::replace(&mut self.key, ::std::vec::Vec::new())
+    }
+
+    // bool linearizable = 2;
+
+
+    pub fn get_linearizable(&self) -> bool {
+        self.linearizable
+    }
+    pub fn clear_linearizable(&mut self) {
+        self.linearizable = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_linearizable(&mut self, v: bool) {
+        self.linearizable = v;
     }
 }
 
@@ -449,7 +472,14 @@ impl ::protobuf::Message for ReadRequest {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.key)?;
+                    ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.key)?;
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.linearizable = tmp;
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -464,7 +494,10 @@ impl ::protobuf::Message for ReadRequest {
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
         if !self.key.is_empty() {
-            my_size += ::protobuf::rt::string_size(1, &self.key);
+            my_size += ::protobuf::rt::bytes_size(1, &self.key);
+        }
+        if self.linearizable != false {
+            my_size += 2;
         }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
@@ -473,7 +506,10 @@ impl ::protobuf::Message for ReadRequest {
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
         if !self.key.is_empty() {
-            os.write_string(1, &self.key)?;
+            os.write_bytes(1, &self.key)?;
+        }
+        if self.linearizable != false {
+            os.write_bool(2, self.linearizable)?;
         }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
@@ -517,11 +553,16 @@ impl ::protobuf::Message for ReadRequest {
         unsafe {
             descriptor.get(|| {
                 let mut fields = ::std::vec::Vec::new();
-                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
                     "key",
                     |m: &ReadRequest| { &m.key },
                     |m: &mut ReadRequest| { &mut m.key },
                 ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                    "linearizable",
+                    |m: &ReadRequest| { &m.linearizable },
+                    |m: &mut ReadRequest| { &mut m.linearizable },
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<ReadRequest>(
                     "ReadRequest",
                     fields,
@@ -545,6 +586,7 @@ impl ::protobuf::Message for ReadRequest {
 impl ::protobuf::Clear for ReadRequest {
     fn clear(&mut self) {
         self.key.clear();
+        self.linearizable = false;
         self.unknown_fields.clear();
     }
 }
@@ -564,7 +606,8 @@ impl ::protobuf::reflect::ProtobufValue for ReadRequest {
 #[derive(PartialEq,Clone,Default)]
 pub struct ReadResponse {
     // message fields
-    pub value: i32,
+    pub value: ::std::vec::Vec<u8>,
+    pub found: bool,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
@@ -581,20 +624,46 @@ impl ReadResponse {
         ::std::default::Default::default()
     }
 
-    // int32 value = 1;
+    // bytes value = 1;
 
 
-    pub fn get_value(&self) -> i32 {
-        self.value
+    pub fn get_value(&self) -> &[u8] {
+        &self.value
     }
     pub fn clear_value(&mut self) {
-        self.value = 0;
+        self.value.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_value(&mut self, v: i32) {
+    pub fn set_value(&mut self, v: ::std::vec::Vec<u8>) {
         self.value = v;
     }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_value(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.value
+    }
+
+    // Take field
+    pub fn take_value(&mut self) -> ::std::vec::Vec<u8> {
+        ::std::mem::replace(&mut self.value, ::std::vec::Vec::new())
+    }
+
+    // bool found = 2;
+
+
+    pub fn get_found(&self) -> bool {
+        self.found
+    }
+    pub fn clear_found(&mut self) {
+        self.found = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_found(&mut self, v: bool) {
+        self.found = v;
+    }
 }
 
 impl ::protobuf::Message for ReadResponse {
@@ -607,11 +676,14 @@ impl ::protobuf::Message for ReadResponse {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
+                    ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.value)?;
+                },
+                2 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    let tmp = is.read_int32()?;
-                    self.value = tmp;
+                    let tmp = is.read_bool()?;
+                    self.found = tmp;
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -625,8 +697,11 @@ impl ::protobuf::Message for ReadResponse {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        if self.value != 0 {
-            my_size += ::protobuf::rt::value_size(1, self.value, ::protobuf::wire_format::WireTypeVarint);
+        if !self.value.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(1, &self.value);
+        }
+        if self.found != false {
+            my_size += 2;
         }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
@@ -634,8 +709,11 @@ impl ::protobuf::Message for ReadResponse {
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        if self.value != 0 {
-            os.write_int32(1, self.value)?;
+        if !self.value.is_empty() {
+            os.write_bytes(1, &self.value)?;
+        }
+        if self.found != false {
+            os.write_bool(2, self.found)?;
         }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
@@ -679,11 +757,16 @@ impl ::protobuf::Message for ReadResponse {
         unsafe {
             descriptor.get(|| {
                 let mut fields = ::std::vec::Vec::new();
-                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt32>(
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
                     "value",
                     |m: &ReadResponse| { &m.value },
                     |m: &mut ReadResponse| { &mut m.value },
                 ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                    "found",
+                    |m: &ReadResponse| { &m.found },
+                    |m: &mut ReadResponse| { &mut m.found },
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<ReadResponse>(
                     "ReadResponse",
                     fields,
@@ -706,7 +789,8 @@ impl ::protobuf::Message for ReadResponse {
 
 impl ::protobuf::Clear for ReadResponse {
     fn clear(&mut self) {
-        self.value = 0;
+        self.value.clear();
+        self.found = false;
         self.unknown_fields.clear();
     }
 }
@@ -724,59 +808,187 @@ impl ::protobuf::reflect::ProtobufValue for ReadResponse {
 }
 
 #[derive(PartialEq,Clone,Default)]
-pub struct Instance {
+pub struct Command {
     // message fields
-    pub replica: u32,
-    pub slot: u32,
+    pub op: Command_Op,
+    pub key: ::std::vec::Vec<u8>,
+    pub value: ::std::vec::Vec<u8>,
+    pub delta: i64,
+    pub expected_found: bool,
+    pub expected: ::std::vec::Vec<u8>,
+    pub commands: ::protobuf::RepeatedField<Command>,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
 }
 
-impl<'a> ::std::default::Default for &'a Instance {
-    fn default() -> &'a Instance {
-        <Instance as ::protobuf::Message>::default_instance()
+impl<'a> ::std::default::Default for &'a Command {
+    fn default() -> &'a Command {
+        <Command as ::protobuf::Message>::default_instance()
     }
 }
 
-impl Instance {
-    pub fn new() -> Instance {
+impl Command {
+    pub fn new() -> Command {
         ::std::default::Default::default()
     }
 
-    // uint32 replica = 1;
+    // .epaxos.Command.Op op = 1;
 
 
-    pub fn get_replica(&self) -> u32 {
-        self.replica
+    pub fn get_op(&self) -> Command_Op {
+        self.op
     }
-    pub fn clear_replica(&mut self) {
-        self.replica = 0;
+    pub fn clear_op(&mut self) {
+        self.op = Command_Op::PUT;
     }
 
     // Param is passed by value, moved
-    pub fn set_replica(&mut self, v: u32) {
-        self.replica = v;
+    pub fn set_op(&mut self, v: Command_Op) {
+        self.op = v;
     }
 
-    // uint32 slot = 2;
+    // bytes key = 2;
 
 
-    pub fn get_slot(&self) -> u32 {
-        self.slot
+    pub fn get_key(&self) -> &[u8] {
+        &self.key
     }
-    pub fn clear_slot(&mut self) {
-        self.slot = 0;
+    pub fn clear_key(&mut self) {
+        self.key.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_slot(&mut self, v: u32) {
-        self.slot = v;
+    pub fn set_key(&mut self, v: ::std::vec::Vec<u8>) {
+        self.key = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_key(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.key
+    }
+
+    // Take field
+    pub fn take_key(&mut self) -> ::std::vec::Vec<u8> {
+        ::std::mem::replace(&mut self.key, ::std::vec::Vec::new())
+    }
+
+    // bytes value = 3;
+
+
+    pub fn get_value(&self) -> &[u8] {
+        &self.value
+    }
+    pub fn clear_value(&mut self) {
+        self.value.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_value(&mut self, v: ::std::vec::Vec<u8>) {
+        self.value = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_value(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.value
+    }
+
+    // Take field
+    pub fn take_value(&mut self) -> ::std::vec::Vec<u8> {
+        ::std::mem::replace(&mut self.value, ::std::vec::Vec::new())
+    }
+
+    // sint64 delta = 4;
+
+
+    pub fn get_delta(&self) -> i64 {
+        self.delta
+    }
+    pub fn clear_delta(&mut self) {
+        self.delta = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_delta(&mut self, v: i64) {
+        self.delta = v;
+    }
+
+    // bool expected_found = 5;
+
+
+    pub fn get_expected_found(&self) -> bool {
+        self.expected_found
+    }
+    pub fn clear_expected_found(&mut self) {
+        self.expected_found = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_expected_found(&mut self, v: bool) {
+        self.expected_found = v;
+    }
+
+    // bytes expected = 6;
+
+
+    pub fn get_expected(&self) -> &[u8] {
+        &self.expected
+    }
+    pub fn clear_expected(&mut self) {
+        self.expected.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_expected(&mut self, v: ::std::vec::Vec<u8>) {
+        self.expected = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_expected(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.expected
+    }
+
+    // Take field
+    pub fn take_expected(&mut self) -> ::std::vec::Vec<u8> {
+        ::std::mem::replace(&mut self.expected, ::std::vec::Vec::new())
+    }
+
+    // repeated .epaxos.Command commands = 7;
+
+
+    pub fn get_commands(&self) -> &[Command] {
+        &self.commands
+    }
+    pub fn clear_commands(&mut self) {
+        self.commands.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_commands(&mut self, v: ::protobuf::RepeatedField<Command>) {
+        self.commands = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_commands(&mut self) -> &mut ::protobuf::RepeatedField<Command> {
+        &mut self.commands
+    }
+
+    // Take field
+    pub fn take_commands(&mut self) -> ::protobuf::RepeatedField<Command> {
+        ::std::mem::replace(&mut self.commands, ::protobuf::RepeatedField::new())
     }
 }
 
-impl ::protobuf::Message for Instance {
+impl ::protobuf::Message for Command {
     fn is_initialized(&self) -> bool {
+        for v in &self.commands {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
         true
     }
 
@@ -785,18 +997,33 @@ impl ::protobuf::Message for Instance {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
+                    ::protobuf::rt::read_proto3_enum_with_unknown_fields_into(wire_type, is, &mut self.op, 1, &mut self.unknown_fields)?
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.key)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.value)?;
+                },
+                4 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    let tmp = is.read_uint32()?;
-                    self.replica = tmp;
+                    let tmp = is.read_sint64()?;
+                    self.delta = tmp;
                 },
-                2 => {
+                5 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    let tmp = is.read_uint32()?;
-                    self.slot = tmp;
+                    let tmp = is.read_bool()?;
+                    self.expected_found = tmp;
+                },
+                6 => {
+                    ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.expected)?;
+                },
+                7 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.commands)?;
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -810,24 +1037,57 @@ impl ::protobuf::Message for Instance {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        if self.replica != 0 {
-            my_size += ::protobuf::rt::value_size(1, self.replica, ::protobuf::wire_format::WireTypeVarint);
+        if self.op != Command_Op::PUT {
+            my_size += ::protobuf::rt::enum_size(1, self.op);
         }
-        if self.slot != 0 {
-            my_size += ::protobuf::rt::value_size(2, self.slot, ::protobuf::wire_format::WireTypeVarint);
+        if !self.key.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(2, &self.key);
+        }
+        if !self.value.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(3, &self.value);
         }
+        if self.delta != 0 {
+            my_size += ::protobuf::rt::value_varint_zigzag_size(4, self.delta);
+        }
+        if self.expected_found != false {
+            my_size += 2;
+        }
+        if !self.expected.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(6, &self.expected);
+        }
+        for value in &self.commands {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        if self.replica != 0 {
-            os.write_uint32(1, self.replica)?;
+        if self.op != Command_Op::PUT {
+            os.write_enum(1, self.op.value())?;
         }
-        if self.slot != 0 {
-            os.write_uint32(2, self.slot)?;
+        if !self.key.is_empty() {
+            os.write_bytes(2, &self.key)?;
+        }
+        if !self.value.is_empty() {
+            os.write_bytes(3, &self.value)?;
+        }
+        if self.delta != 0 {
+            os.write_sint64(4, self.delta)?;
+        }
+        if self.expected_found != false {
+            os.write_bool(5, self.expected_found)?;
+        }
+        if !self.expected.is_empty() {
+            os.write_bytes(6, &self.expected)?;
         }
+        for v in &self.commands {
+            os.write_tag(7, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -858,8 +1118,8 @@ impl ::protobuf::Message for Instance {
         Self::descriptor_static()
     }
 
-    fn new() -> Instance {
-        Instance::new()
+    fn new() -> Command {
+        Command::new()
     }
 
     fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
@@ -870,18 +1130,43 @@ impl ::protobuf::Message for Instance {
         unsafe {
             descriptor.get(|| {
                 let mut fields = ::std::vec::Vec::new();
-                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
-                    "replica",
-                    |m: &Instance| { &m.replica },
-                    |m: &mut Instance| { &mut m.replica },
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<Command_Op>>(
+                    "op",
+                    |m: &Command| { &m.op },
+                    |m: &mut Command| { &mut m.op },
                 ));
-                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
-                    "slot",
-                    |m: &Instance| { &m.slot },
-                    |m: &mut Instance| { &mut m.slot },
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                    "key",
+                    |m: &Command| { &m.key },
+                    |m: &mut Command| { &mut m.key },
                 ));
-                ::protobuf::reflect::MessageDescriptor::new::<Instance>(
-                    "Instance",
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                    "value",
+                    |m: &Command| { &m.value },
+                    |m: &mut Command| { &mut m.value },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeSint64>(
+                    "delta",
+                    |m: &Command| { &m.delta },
+                    |m: &mut Command| { &mut m.delta },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                    "expected_found",
+                    |m: &Command| { &m.expected_found },
+                    |m: &mut Command| { &mut m.expected_found },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                    "expected",
+                    |m: &Command| { &m.expected },
+                    |m: &mut Command| { &mut m.expected },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Command>>(
+                    "commands",
+                    |m: &Command| { &m.commands },
+                    |m: &mut Command| { &mut m.commands },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<Command>(
+                    "Command",
                     fields,
                     file_descriptor_proto()
                 )
@@ -889,180 +1174,4488 @@ impl ::protobuf::Message for Instance {
         }
     }
 
-    fn default_instance() -> &'static Instance {
-        static mut instance: ::protobuf::lazy::Lazy<Instance> = ::protobuf::lazy::Lazy {
+    fn default_instance() -> &'static Command {
+        static mut instance: ::protobuf::lazy::Lazy<Command> = ::protobuf::lazy::Lazy {
             lock: ::protobuf::lazy::ONCE_INIT,
-            ptr: 0 as *const Instance,
+            ptr: 0 as *const Command,
         };
         unsafe {
-            instance.get(Instance::new)
+            instance.get(Command::new)
         }
     }
 }
 
-impl ::protobuf::Clear for Instance {
+impl ::protobuf::Clear for Command {
     fn clear(&mut self) {
-        self.replica = 0;
-        self.slot = 0;
+        self.op = Command_Op::PUT;
+        self.key.clear();
+        self.value.clear();
+        self.delta = 0;
+        self.expected_found = false;
+        self.expected.clear();
+        self.commands.clear();
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::fmt::Debug for Instance {
+impl ::std::fmt::Debug for Command {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for Instance {
+impl ::protobuf::reflect::ProtobufValue for Command {
     fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
         ::protobuf::reflect::ProtobufValueRef::Message(self)
     }
 }
 
-#[derive(PartialEq,Clone,Default)]
-pub struct Payload {
-    // message fields
-    pub write_req: ::protobuf::SingularPtrField<WriteRequest>,
-    pub seq: u32,
-    pub deps: ::protobuf::RepeatedField<Instance>,
-    pub instance: ::protobuf::SingularPtrField<Instance>,
-    // special fields
-    pub unknown_fields: ::protobuf::UnknownFields,
-    pub cached_size: ::protobuf::CachedSize,
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub enum Command_Op {
+    PUT = 0,
+    DELETE = 1,
+    INCREMENT = 2,
+    APPEND = 3,
+    CAS = 4,
+    GET = 5,
+    MULTI = 6,
 }
 
-impl<'a> ::std::default::Default for &'a Payload {
-    fn default() -> &'a Payload {
-        <Payload as ::protobuf::Message>::default_instance()
+impl ::protobuf::ProtobufEnum for Command_Op {
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<Command_Op> {
+        match value {
+            0 => ::std::option::Option::Some(Command_Op::PUT),
+            1 => ::std::option::Option::Some(Command_Op::DELETE),
+            2 => ::std::option::Option::Some(Command_Op::INCREMENT),
+            3 => ::std::option::Option::Some(Command_Op::APPEND),
+            4 => ::std::option::Option::Some(Command_Op::CAS),
+            5 => ::std::option::Option::Some(Command_Op::GET),
+            6 => ::std::option::Option::Some(Command_Op::MULTI),
+            _ => ::std::option::Option::None
+        }
     }
-}
 
-impl Payload {
-    pub fn new() -> Payload {
-        ::std::default::Default::default()
+    fn values() -> &'static [Self] {
+        static values: &'static [Command_Op] = &[
+            Command_Op::PUT,
+            Command_Op::DELETE,
+            Command_Op::INCREMENT,
+            Command_Op::APPEND,
+            Command_Op::CAS,
+            Command_Op::GET,
+            Command_Op::MULTI,
+        ];
+        values
     }
 
-    // .epaxos.WriteRequest write_req = 2;
+    fn enum_descriptor_static() -> &'static ::protobuf::reflect::EnumDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::EnumDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::EnumDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                ::protobuf::reflect::EnumDescriptor::new("Command_Op", file_descriptor_proto())
+            })
+        }
+    }
+}
 
+impl ::std::marker::Copy for Command_Op {
+}
 
-    pub fn get_write_req(&self) -> &WriteRequest {
-        self.write_req.as_ref().unwrap_or_else(|| WriteRequest::default_instance())
-    }
-    pub fn clear_write_req(&mut self) {
-        self.write_req.clear();
+impl ::std::default::Default for Command_Op {
+    fn default() -> Self {
+        Command_Op::PUT
     }
+}
 
-    pub fn has_write_req(&self) -> bool {
-        self.write_req.is_some()
+impl ::protobuf::reflect::ProtobufValue for Command_Op {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Enum(self.descriptor())
     }
+}
 
-    // Param is passed by value, moved
-    pub fn set_write_req(&mut self, v: WriteRequest) {
-        self.write_req = ::protobuf::SingularPtrField::some(v);
+#[derive(PartialEq,Clone,Default)]
+pub struct CommandResult {
+    // message fields
+    pub ok: bool,
+    pub found: bool,
+    pub value: ::std::vec::Vec<u8>,
+    pub results: ::protobuf::RepeatedField<CommandResult>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a CommandResult {
+    fn default() -> &'a CommandResult {
+        <CommandResult as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl CommandResult {
+    pub fn new() -> CommandResult {
+        ::std::default::Default::default()
+    }
+
+    // bool ok = 1;
+
+
+    pub fn get_ok(&self) -> bool {
+        self.ok
+    }
+    pub fn clear_ok(&mut self) {
+        self.ok = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_ok(&mut self, v: bool) {
+        self.ok = v;
+    }
+
+    // bool found = 2;
+
+
+    pub fn get_found(&self) -> bool {
+        self.found
+    }
+    pub fn clear_found(&mut self) {
+        self.found = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_found(&mut self, v: bool) {
+        self.found = v;
+    }
+
+    // bytes value = 3;
+
+
+    pub fn get_value(&self) -> &[u8] {
+        &self.value
+    }
+    pub fn clear_value(&mut self) {
+        self.value.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_value(&mut self, v: ::std::vec::Vec<u8>) {
+        self.value = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_value(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.value
+    }
+
+    // Take field
+    pub fn take_value(&mut self) -> ::std::vec::Vec<u8> {
+        ::std::mem::replace(&mut self.value, ::std::vec::Vec::new())
+    }
+
+    // repeated .epaxos.CommandResult results = 4;
+
+
+    pub fn get_results(&self) -> &[CommandResult] {
+        &self.results
+    }
+    pub fn clear_results(&mut self) {
+        self.results.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_results(&mut self, v: ::protobuf::RepeatedField<CommandResult>) {
+        self.results = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_results(&mut self) -> &mut ::protobuf::RepeatedField<CommandResult> {
+        &mut self.results
+    }
+
+    // Take field
+    pub fn take_results(&mut self) -> ::protobuf::RepeatedField<CommandResult> {
+        ::std::mem::replace(&mut self.results, ::protobuf::RepeatedField::new())
+    }
+}
+
+impl ::protobuf::Message for CommandResult {
+    fn is_initialized(&self) -> bool {
+        for v in &self.results {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.ok = tmp;
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.found = tmp;
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.value)?;
+                },
+                4 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.results)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.ok != false {
+            my_size += 2;
+        }
+        if self.found != false {
+            my_size += 2;
+        }
+        if !self.value.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(3, &self.value);
+        }
+        for value in &self.results {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if self.ok != false {
+            os.write_bool(1, self.ok)?;
+        }
+        if self.found != false {
+            os.write_bool(2, self.found)?;
+        }
+        if !self.value.is_empty() {
+            os.write_bytes(3, &self.value)?;
+        }
+        for v in &self.results {
+            os.write_tag(4, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> CommandResult {
+        CommandResult::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                    "ok",
+                    |m: &CommandResult| { &m.ok },
+                    |m: &mut CommandResult| { &mut m.ok },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                    "found",
+                    |m: &CommandResult| { &m.found },
+                    |m: &mut CommandResult| { &mut m.found },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                    "value",
+                    |m: &CommandResult| { &m.value },
+                    |m: &mut CommandResult| { &mut m.value },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<CommandResult>>(
+                    "results",
+                    |m: &CommandResult| { &m.results },
+                    |m: &mut CommandResult| { &mut m.results },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<CommandResult>(
+                    "CommandResult",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static CommandResult {
+        static mut instance: ::protobuf::lazy::Lazy<CommandResult> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const CommandResult,
+        };
+        unsafe {
+            instance.get(CommandResult::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for CommandResult {
+    fn clear(&mut self) {
+        self.ok = false;
+        self.found = false;
+        self.value.clear();
+        self.results.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for CommandResult {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for CommandResult {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Instance {
+    // message fields
+    pub replica: u32,
+    pub slot: u32,
+    pub epoch: u32,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a Instance {
+    fn default() -> &'a Instance {
+        <Instance as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Instance {
+    pub fn new() -> Instance {
+        ::std::default::Default::default()
+    }
+
+    // uint32 replica = 1;
+
+
+    pub fn get_replica(&self) -> u32 {
+        self.replica
+    }
+    pub fn clear_replica(&mut self) {
+        self.replica = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_replica(&mut self, v: u32) {
+        self.replica = v;
+    }
+
+    // uint32 slot = 2;
+
+
+    pub fn get_slot(&self) -> u32 {
+        self.slot
+    }
+    pub fn clear_slot(&mut self) {
+        self.slot = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_slot(&mut self, v: u32) {
+        self.slot = v;
+    }
+
+    // uint32 epoch = 3;
+
+
+    pub fn get_epoch(&self) -> u32 {
+        self.epoch
+    }
+    pub fn clear_epoch(&mut self) {
+        self.epoch = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_epoch(&mut self, v: u32) {
+        self.epoch = v;
+    }
+}
+
+impl ::protobuf::Message for Instance {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.replica = tmp;
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.slot = tmp;
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.epoch = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.replica != 0 {
+            my_size += ::protobuf::rt::value_size(1, self.replica, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.slot != 0 {
+            my_size += ::protobuf::rt::value_size(2, self.slot, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.epoch != 0 {
+            my_size += ::protobuf::rt::value_size(3, self.epoch, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if self.replica != 0 {
+            os.write_uint32(1, self.replica)?;
+        }
+        if self.slot != 0 {
+            os.write_uint32(2, self.slot)?;
+        }
+        if self.epoch != 0 {
+            os.write_uint32(3, self.epoch)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> Instance {
+        Instance::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "replica",
+                    |m: &Instance| { &m.replica },
+                    |m: &mut Instance| { &mut m.replica },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "slot",
+                    |m: &Instance| { &m.slot },
+                    |m: &mut Instance| { &mut m.slot },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "epoch",
+                    |m: &Instance| { &m.epoch },
+                    |m: &mut Instance| { &mut m.epoch },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<Instance>(
+                    "Instance",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static Instance {
+        static mut instance: ::protobuf::lazy::Lazy<Instance> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const Instance,
+        };
+        unsafe {
+            instance.get(Instance::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for Instance {
+    fn clear(&mut self) {
+        self.replica = 0;
+        self.slot = 0;
+        self.epoch = 0;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Instance {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Instance {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct ReplicaInfo {
+    // message fields
+    pub id: u32,
+    pub host: ::std::string::String,
+    pub port: u32,
+    pub region: ::std::string::String,
+    pub quorum: ::std::vec::Vec<u32>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a ReplicaInfo {
+    fn default() -> &'a ReplicaInfo {
+        <ReplicaInfo as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl ReplicaInfo {
+    pub fn new() -> ReplicaInfo {
+        ::std::default::Default::default()
+    }
+
+    // uint32 id = 1;
+
+
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+    pub fn clear_id(&mut self) {
+        self.id = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_id(&mut self, v: u32) {
+        self.id = v;
+    }
+
+    // string host = 2;
+
+
+    pub fn get_host(&self) -> &str {
+        &self.host
+    }
+    pub fn clear_host(&mut self) {
+        self.host.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_host(&mut self, v: ::std::string::String) {
+        self.host = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_host(&mut self) -> &mut ::std::string::String {
+        &mut self.host
+    }
+
+    // Take field
+    pub fn take_host(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.host, ::std::string::String::new())
+    }
+
+    // uint32 port = 3;
+
+
+    pub fn get_port(&self) -> u32 {
+        self.port
+    }
+    pub fn clear_port(&mut self) {
+        self.port = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_port(&mut self, v: u32) {
+        self.port = v;
+    }
+
+    // string region = 4;
+
+
+    pub fn get_region(&self) -> &str {
+        &self.region
+    }
+    pub fn clear_region(&mut self) {
+        self.region.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_region(&mut self, v: ::std::string::String) {
+        self.region = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_region(&mut self) -> &mut ::std::string::String {
+        &mut self.region
+    }
+
+    // Take field
+    pub fn take_region(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.region, ::std::string::String::new())
+    }
+
+    // repeated uint32 quorum = 5;
+
+
+    pub fn get_quorum(&self) -> &[u32] {
+        &self.quorum
+    }
+    pub fn clear_quorum(&mut self) {
+        self.quorum.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_quorum(&mut self, v: ::std::vec::Vec<u32>) {
+        self.quorum = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_quorum(&mut self) -> &mut ::std::vec::Vec<u32> {
+        &mut self.quorum
+    }
+
+    // Take field
+    pub fn take_quorum(&mut self) -> ::std::vec::Vec<u32> {
+        ::std::mem::replace(&mut self.quorum, ::std::vec::Vec::new())
+    }
+}
+
+impl ::protobuf::Message for ReplicaInfo {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.id = tmp;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.host)?;
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.port = tmp;
+                },
+                4 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.region)?;
+                },
+                5 => {
+                    ::protobuf::rt::read_repeated_uint32_into(wire_type, is, &mut self.quorum)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.id != 0 {
+            my_size += ::protobuf::rt::value_size(1, self.id, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if !self.host.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.host);
+        }
+        if self.port != 0 {
+            my_size += ::protobuf::rt::value_size(3, self.port, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if !self.region.is_empty() {
+            my_size += ::protobuf::rt::string_size(4, &self.region);
+        }
+        for value in &self.quorum {
+            my_size += ::protobuf::rt::value_size(5, *value, ::protobuf::wire_format::WireTypeVarint);
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if self.id != 0 {
+            os.write_uint32(1, self.id)?;
+        }
+        if !self.host.is_empty() {
+            os.write_string(2, &self.host)?;
+        }
+        if self.port != 0 {
+            os.write_uint32(3, self.port)?;
+        }
+        if !self.region.is_empty() {
+            os.write_string(4, &self.region)?;
+        }
+        for v in &self.quorum {
+            os.write_uint32(5, *v)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> ReplicaInfo {
+        ReplicaInfo::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "id",
+                    |m: &ReplicaInfo| { &m.id },
+                    |m: &mut ReplicaInfo| { &mut m.id },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "host",
+                    |m: &ReplicaInfo| { &m.host },
+                    |m: &mut ReplicaInfo| { &mut m.host },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "port",
+                    |m: &ReplicaInfo| { &m.port },
+                    |m: &mut ReplicaInfo| { &mut m.port },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "region",
+                    |m: &ReplicaInfo| { &m.region },
+                    |m: &mut ReplicaInfo| { &mut m.region },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_vec_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "quorum",
+                    |m: &ReplicaInfo| { &m.quorum },
+                    |m: &mut ReplicaInfo| { &mut m.quorum },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<ReplicaInfo>(
+                    "ReplicaInfo",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static ReplicaInfo {
+        static mut instance: ::protobuf::lazy::Lazy<ReplicaInfo> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ReplicaInfo,
+        };
+        unsafe {
+            instance.get(ReplicaInfo::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for ReplicaInfo {
+    fn clear(&mut self) {
+        self.id = 0;
+        self.host.clear();
+        self.port = 0;
+        self.region.clear();
+        self.quorum.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for ReplicaInfo {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for ReplicaInfo {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Reconfiguration {
+    // message fields
+    pub change: MembershipChange,
+    pub replica: ::protobuf::SingularPtrField<ReplicaInfo>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a Reconfiguration {
+    fn default() -> &'a Reconfiguration {
+        <Reconfiguration as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Reconfiguration {
+    pub fn new() -> Reconfiguration {
+        ::std::default::Default::default()
+    }
+
+    // .epaxos.MembershipChange change = 1;
+
+
+    pub fn get_change(&self) -> MembershipChange {
+        self.change
+    }
+    pub fn clear_change(&mut self) {
+        self.change = MembershipChange::ADD;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_change(&mut self, v: MembershipChange) {
+        self.change = v;
+    }
+
+    // .epaxos.ReplicaInfo replica = 2;
+
+
+    pub fn get_replica(&self) -> &ReplicaInfo {
+        self.replica.as_ref().unwrap_or_else(|| ReplicaInfo::default_instance())
+    }
+    pub fn clear_replica(&mut self) {
+        self.replica.clear();
+    }
+
+    pub fn has_replica(&self) -> bool {
+        self.replica.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_replica(&mut self, v: ReplicaInfo) {
+        self.replica = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_replica(&mut self) -> &mut ReplicaInfo {
+        if self.replica.is_none() {
+            self.replica.set_default();
+        }
+        self.replica.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_replica(&mut self) -> ReplicaInfo {
+        self.replica.take().unwrap_or_else(|| ReplicaInfo::new())
+    }
+}
+
+impl ::protobuf::Message for Reconfiguration {
+    fn is_initialized(&self) -> bool {
+        for v in &self.replica {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_proto3_enum_with_unknown_fields_into(wire_type, is, &mut self.change, 1, &mut self.unknown_fields)?
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.replica)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.change != MembershipChange::ADD {
+            my_size += ::protobuf::rt::enum_size(1, self.change);
+        }
+        if let Some(ref v) = self.replica.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if self.change != MembershipChange::ADD {
+            os.write_enum(1, self.change.value())?;
+        }
+        if let Some(ref v) = self.replica.as_ref() {
+            os.write_tag(2, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> Reconfiguration {
+        Reconfiguration::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<MembershipChange>>(
+                    "change",
+                    |m: &Reconfiguration| { &m.change },
+                    |m: &mut Reconfiguration| { &mut m.change },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<ReplicaInfo>>(
+                    "replica",
+                    |m: &Reconfiguration| { &m.replica },
+                    |m: &mut Reconfiguration| { &mut m.replica },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<Reconfiguration>(
+                    "Reconfiguration",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static Reconfiguration {
+        static mut instance: ::protobuf::lazy::Lazy<Reconfiguration> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const Reconfiguration,
+        };
+        unsafe {
+            instance.get(Reconfiguration::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for Reconfiguration {
+    fn clear(&mut self) {
+        self.change = MembershipChange::ADD;
+        self.replica.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Reconfiguration {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Reconfiguration {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Payload {
+    // message fields
+    pub seq: u32,
+    pub deps: ::protobuf::RepeatedField<Instance>,
+    pub instance: ::protobuf::SingularPtrField<Instance>,
+    pub noop: bool,
+    pub ballot: ::protobuf::SingularPtrField<Ballot>,
+    pub reconfig: ::protobuf::SingularPtrField<Reconfiguration>,
+    pub command: ::std::vec::Vec<u8>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a Payload {
+    fn default() -> &'a Payload {
+        <Payload as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Payload {
+    pub fn new() -> Payload {
+        ::std::default::Default::default()
+    }
+
+    // uint32 seq = 3;
+
+
+    pub fn get_seq(&self) -> u32 {
+        self.seq
+    }
+    pub fn clear_seq(&mut self) {
+        self.seq = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_seq(&mut self, v: u32) {
+        self.seq = v;
+    }
+
+    // repeated .epaxos.Instance deps = 4;
+
+
+    pub fn get_deps(&self) -> &[Instance] {
+        &self.deps
+    }
+    pub fn clear_deps(&mut self) {
+        self.deps.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_deps(&mut self, v: ::protobuf::RepeatedField<Instance>) {
+        self.deps = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_deps(&mut self) -> &mut ::protobuf::RepeatedField<Instance> {
+        &mut self.deps
+    }
+
+    // Take field
+    pub fn take_deps(&mut self) -> ::protobuf::RepeatedField<Instance> {
+        ::std::mem::replace(&mut self.deps, ::protobuf::RepeatedField::new())
+    }
+
+    // .epaxos.Instance instance = 5;
+
+
+    pub fn get_instance(&self) -> &Instance {
+        self.instance.as_ref().unwrap_or_else(|| Instance::default_instance())
+    }
+    pub fn clear_instance(&mut self) {
+        self.instance.clear();
+    }
+
+    pub fn has_instance(&self) -> bool {
+        self.instance.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_instance(&mut self, v: Instance) {
+        self.instance = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_instance(&mut self) -> &mut Instance {
+        if self.instance.is_none() {
+            self.instance.set_default();
+        }
+        self.instance.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_instance(&mut self) -> Instance {
+        self.instance.take().unwrap_or_else(|| Instance::new())
+    }
+
+    // bool noop = 6;
+
+
+    pub fn get_noop(&self) -> bool {
+        self.noop
+    }
+    pub fn clear_noop(&mut self) {
+        self.noop = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_noop(&mut self, v: bool) {
+        self.noop = v;
+    }
+
+    // .epaxos.Ballot ballot = 7;
+
+
+    pub fn get_ballot(&self) -> &Ballot {
+        self.ballot.as_ref().unwrap_or_else(|| Ballot::default_instance())
+    }
+    pub fn clear_ballot(&mut self) {
+        self.ballot.clear();
+    }
+
+    pub fn has_ballot(&self) -> bool {
+        self.ballot.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_ballot(&mut self, v: Ballot) {
+        self.ballot = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_ballot(&mut self) -> &mut Ballot {
+        if self.ballot.is_none() {
+            self.ballot.set_default();
+        }
+        self.ballot.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_ballot(&mut self) -> Ballot {
+        self.ballot.take().unwrap_or_else(|| Ballot::new())
+    }
+
+    // .epaxos.Reconfiguration reconfig = 8;
+
+
+    pub fn get_reconfig(&self) -> &Reconfiguration {
+        self.reconfig.as_ref().unwrap_or_else(|| Reconfiguration::default_instance())
+    }
+    pub fn clear_reconfig(&mut self) {
+        self.reconfig.clear();
+    }
+
+    pub fn has_reconfig(&self) -> bool {
+        self.reconfig.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_reconfig(&mut self, v: Reconfiguration) {
+        self.reconfig = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_reconfig(&mut self) -> &mut Reconfiguration {
+        if self.reconfig.is_none() {
+            self.reconfig.set_default();
+        }
+        self.reconfig.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_reconfig(&mut self) -> Reconfiguration {
+        self.reconfig.take().unwrap_or_else(|| Reconfiguration::new())
+    }
+
+    // bytes command = 9;
+
+
+    pub fn get_command(&self) -> &[u8] {
+        &self.command
+    }
+    pub fn clear_command(&mut self) {
+        self.command.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_command(&mut self, v: ::std::vec::Vec<u8>) {
+        self.command = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_command(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.command
+    }
+
+    // Take field
+    pub fn take_command(&mut self) -> ::std::vec::Vec<u8> {
+        ::std::mem::replace(&mut self.command, ::std::vec::Vec::new())
+    }
+}
+
+impl ::protobuf::Message for Payload {
+    fn is_initialized(&self) -> bool {
+        for v in &self.deps {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.instance {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.ballot {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.reconfig {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.seq = tmp;
+                },
+                4 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.deps)?;
+                },
+                5 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.instance)?;
+                },
+                6 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.noop = tmp;
+                },
+                7 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.ballot)?;
+                },
+                8 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.reconfig)?;
+                },
+                9 => {
+                    ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.command)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.seq != 0 {
+            my_size += ::protobuf::rt::value_size(3, self.seq, ::protobuf::wire_format::WireTypeVarint);
+        }
+        for value in &self.deps {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        if let Some(ref v) = self.instance.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        if self.noop != false {
+            my_size += 2;
+        }
+        if let Some(ref v) = self.ballot.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        if let Some(ref v) = self.reconfig.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        if !self.command.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(9, &self.command);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if self.seq != 0 {
+            os.write_uint32(3, self.seq)?;
+        }
+        for v in &self.deps {
+            os.write_tag(4, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        if let Some(ref v) = self.instance.as_ref() {
+            os.write_tag(5, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if self.noop != false {
+            os.write_bool(6, self.noop)?;
+        }
+        if let Some(ref v) = self.ballot.as_ref() {
+            os.write_tag(7, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if let Some(ref v) = self.reconfig.as_ref() {
+            os.write_tag(8, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if !self.command.is_empty() {
+            os.write_bytes(9, &self.command)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> Payload {
+        Payload::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "seq",
+                    |m: &Payload| { &m.seq },
+                    |m: &mut Payload| { &mut m.seq },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Instance>>(
+                    "deps",
+                    |m: &Payload| { &m.deps },
+                    |m: &mut Payload| { &mut m.deps },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Instance>>(
+                    "instance",
+                    |m: &Payload| { &m.instance },
+                    |m: &mut Payload| { &mut m.instance },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                    "noop",
+                    |m: &Payload| { &m.noop },
+                    |m: &mut Payload| { &mut m.noop },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Ballot>>(
+                    "ballot",
+                    |m: &Payload| { &m.ballot },
+                    |m: &mut Payload| { &mut m.ballot },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Reconfiguration>>(
+                    "reconfig",
+                    |m: &Payload| { &m.reconfig },
+                    |m: &mut Payload| { &mut m.reconfig },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                    "command",
+                    |m: &Payload| { &m.command },
+                    |m: &mut Payload| { &mut m.command },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<Payload>(
+                    "Payload",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static Payload {
+        static mut instance: ::protobuf::lazy::Lazy<Payload> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const Payload,
+        };
+        unsafe {
+            instance.get(Payload::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for Payload {
+    fn clear(&mut self) {
+        self.seq = 0;
+        self.deps.clear();
+        self.instance.clear();
+        self.noop = false;
+        self.ballot.clear();
+        self.reconfig.clear();
+        self.command.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Payload {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Payload {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct PreAcceptOKPayload {
+    // message fields
+    pub ok: bool,
+    pub ballot: ::protobuf::SingularPtrField<Ballot>,
+    pub payload: ::protobuf::SingularPtrField<Payload>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a PreAcceptOKPayload {
+    fn default() -> &'a PreAcceptOKPayload {
+        <PreAcceptOKPayload as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl PreAcceptOKPayload {
+    pub fn new() -> PreAcceptOKPayload {
+        ::std::default::Default::default()
+    }
+
+    // bool ok = 1;
+
+
+    pub fn get_ok(&self) -> bool {
+        self.ok
+    }
+    pub fn clear_ok(&mut self) {
+        self.ok = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_ok(&mut self, v: bool) {
+        self.ok = v;
+    }
+
+    // .epaxos.Ballot ballot = 2;
+
+
+    pub fn get_ballot(&self) -> &Ballot {
+        self.ballot.as_ref().unwrap_or_else(|| Ballot::default_instance())
+    }
+    pub fn clear_ballot(&mut self) {
+        self.ballot.clear();
+    }
+
+    pub fn has_ballot(&self) -> bool {
+        self.ballot.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_ballot(&mut self, v: Ballot) {
+        self.ballot = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_ballot(&mut self) -> &mut Ballot {
+        if self.ballot.is_none() {
+            self.ballot.set_default();
+        }
+        self.ballot.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_ballot(&mut self) -> Ballot {
+        self.ballot.take().unwrap_or_else(|| Ballot::new())
+    }
+
+    // .epaxos.Payload payload = 3;
+
+
+    pub fn get_payload(&self) -> &Payload {
+        self.payload.as_ref().unwrap_or_else(|| Payload::default_instance())
+    }
+    pub fn clear_payload(&mut self) {
+        self.payload.clear();
+    }
+
+    pub fn has_payload(&self) -> bool {
+        self.payload.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_payload(&mut self, v: Payload) {
+        self.payload = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_payload(&mut self) -> &mut Payload {
+        if self.payload.is_none() {
+            self.payload.set_default();
+        }
+        self.payload.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_payload(&mut self) -> Payload {
+        self.payload.take().unwrap_or_else(|| Payload::new())
+    }
+}
+
+impl ::protobuf::Message for PreAcceptOKPayload {
+    fn is_initialized(&self) -> bool {
+        for v in &self.ballot {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.payload {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.ok = tmp;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.ballot)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.payload)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.ok != false {
+            my_size += 2;
+        }
+        if let Some(ref v) = self.ballot.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        if let Some(ref v) = self.payload.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if self.ok != false {
+            os.write_bool(1, self.ok)?;
+        }
+        if let Some(ref v) = self.ballot.as_ref() {
+            os.write_tag(2, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if let Some(ref v) = self.payload.as_ref() {
+            os.write_tag(3, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> PreAcceptOKPayload {
+        PreAcceptOKPayload::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                    "ok",
+                    |m: &PreAcceptOKPayload| { &m.ok },
+                    |m: &mut PreAcceptOKPayload| { &mut m.ok },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Ballot>>(
+                    "ballot",
+                    |m: &PreAcceptOKPayload| { &m.ballot },
+                    |m: &mut PreAcceptOKPayload| { &mut m.ballot },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Payload>>(
+                    "payload",
+                    |m: &PreAcceptOKPayload| { &m.payload },
+                    |m: &mut PreAcceptOKPayload| { &mut m.payload },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<PreAcceptOKPayload>(
+                    "PreAcceptOKPayload",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static PreAcceptOKPayload {
+        static mut instance: ::protobuf::lazy::Lazy<PreAcceptOKPayload> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const PreAcceptOKPayload,
+        };
+        unsafe {
+            instance.get(PreAcceptOKPayload::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for PreAcceptOKPayload {
+    fn clear(&mut self) {
+        self.ok = false;
+        self.ballot.clear();
+        self.payload.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for PreAcceptOKPayload {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for PreAcceptOKPayload {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct AcceptOKPayload {
+    // message fields
+    pub ok: bool,
+    pub ballot: ::protobuf::SingularPtrField<Ballot>,
+    pub instance: ::protobuf::SingularPtrField<Instance>,
+    pub command: ::std::vec::Vec<u8>,
+    pub committed: ::protobuf::SingularPtrField<Payload>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a AcceptOKPayload {
+    fn default() -> &'a AcceptOKPayload {
+        <AcceptOKPayload as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl AcceptOKPayload {
+    pub fn new() -> AcceptOKPayload {
+        ::std::default::Default::default()
+    }
+
+    // bool ok = 2;
+
+
+    pub fn get_ok(&self) -> bool {
+        self.ok
+    }
+    pub fn clear_ok(&mut self) {
+        self.ok = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_ok(&mut self, v: bool) {
+        self.ok = v;
+    }
+
+    // .epaxos.Ballot ballot = 3;
+
+
+    pub fn get_ballot(&self) -> &Ballot {
+        self.ballot.as_ref().unwrap_or_else(|| Ballot::default_instance())
+    }
+    pub fn clear_ballot(&mut self) {
+        self.ballot.clear();
+    }
+
+    pub fn has_ballot(&self) -> bool {
+        self.ballot.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_ballot(&mut self, v: Ballot) {
+        self.ballot = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_ballot(&mut self) -> &mut Ballot {
+        if self.ballot.is_none() {
+            self.ballot.set_default();
+        }
+        self.ballot.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_ballot(&mut self) -> Ballot {
+        self.ballot.take().unwrap_or_else(|| Ballot::new())
+    }
+
+    // .epaxos.Instance instance = 4;
+
+
+    pub fn get_instance(&self) -> &Instance {
+        self.instance.as_ref().unwrap_or_else(|| Instance::default_instance())
+    }
+    pub fn clear_instance(&mut self) {
+        self.instance.clear();
+    }
+
+    pub fn has_instance(&self) -> bool {
+        self.instance.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_instance(&mut self, v: Instance) {
+        self.instance = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_instance(&mut self) -> &mut Instance {
+        if self.instance.is_none() {
+            self.instance.set_default();
+        }
+        self.instance.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_instance(&mut self) -> Instance {
+        self.instance.take().unwrap_or_else(|| Instance::new())
+    }
+
+    // bytes command = 5;
+
+
+    pub fn get_command(&self) -> &[u8] {
+        &self.command
+    }
+    pub fn clear_command(&mut self) {
+        self.command.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_command(&mut self, v: ::std::vec::Vec<u8>) {
+        self.command = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_command(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.command
+    }
+
+    // Take field
+    pub fn take_command(&mut self) -> ::std::vec::Vec<u8> {
+        ::std::mem::replace(&mut self.command, ::std::vec::Vec::new())
+    }
+
+    // .epaxos.Payload committed = 6;
+
+
+    pub fn get_committed(&self) -> &Payload {
+        self.committed.as_ref().unwrap_or_else(|| Payload::default_instance())
+    }
+    pub fn clear_committed(&mut self) {
+        self.committed.clear();
+    }
+
+    pub fn has_committed(&self) -> bool {
+        self.committed.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_committed(&mut self, v: Payload) {
+        self.committed = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_committed(&mut self) -> &mut Payload {
+        if self.committed.is_none() {
+            self.committed.set_default();
+        }
+        self.committed.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_committed(&mut self) -> Payload {
+        self.committed.take().unwrap_or_else(|| Payload::new())
+    }
+}
+
+impl ::protobuf::Message for AcceptOKPayload {
+    fn is_initialized(&self) -> bool {
+        for v in &self.ballot {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.instance {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.committed {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.ok = tmp;
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.ballot)?;
+                },
+                4 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.instance)?;
+                },
+                5 => {
+                    ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.command)?;
+                },
+                6 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.committed)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.ok != false {
+            my_size += 2;
+        }
+        if let Some(ref v) = self.ballot.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        if let Some(ref v) = self.instance.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        if !self.command.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(5, &self.command);
+        }
+        if let Some(ref v) = self.committed.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if self.ok != false {
+            os.write_bool(2, self.ok)?;
+        }
+        if let Some(ref v) = self.ballot.as_ref() {
+            os.write_tag(3, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if let Some(ref v) = self.instance.as_ref() {
+            os.write_tag(4, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if !self.command.is_empty() {
+            os.write_bytes(5, &self.command)?;
+        }
+        if let Some(ref v) = self.committed.as_ref() {
+            os.write_tag(6, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> AcceptOKPayload {
+        AcceptOKPayload::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                    "ok",
+                    |m: &AcceptOKPayload| { &m.ok },
+                    |m: &mut AcceptOKPayload| { &mut m.ok },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Ballot>>(
+                    "ballot",
+                    |m: &AcceptOKPayload| { &m.ballot },
+                    |m: &mut AcceptOKPayload| { &mut m.ballot },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Instance>>(
+                    "instance",
+                    |m: &AcceptOKPayload| { &m.instance },
+                    |m: &mut AcceptOKPayload| { &mut m.instance },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                    "command",
+                    |m: &AcceptOKPayload| { &m.command },
+                    |m: &mut AcceptOKPayload| { &mut m.command },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Payload>>(
+                    "committed",
+                    |m: &AcceptOKPayload| { &m.committed },
+                    |m: &mut AcceptOKPayload| { &mut m.committed },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<AcceptOKPayload>(
+                    "AcceptOKPayload",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static AcceptOKPayload {
+        static mut instance: ::protobuf::lazy::Lazy<AcceptOKPayload> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const AcceptOKPayload,
+        };
+        unsafe {
+            instance.get(AcceptOKPayload::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for AcceptOKPayload {
+    fn clear(&mut self) {
+        self.ok = false;
+        self.ballot.clear();
+        self.instance.clear();
+        self.command.clear();
+        self.committed.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for AcceptOKPayload {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for AcceptOKPayload {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Ballot {
+    // message fields
+    pub epoch: u32,
+    pub number: u32,
+    pub replica: u32,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a Ballot {
+    fn default() -> &'a Ballot {
+        <Ballot as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Ballot {
+    pub fn new() -> Ballot {
+        ::std::default::Default::default()
+    }
+
+    // uint32 epoch = 1;
+
+
+    pub fn get_epoch(&self) -> u32 {
+        self.epoch
+    }
+    pub fn clear_epoch(&mut self) {
+        self.epoch = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_epoch(&mut self, v: u32) {
+        self.epoch = v;
+    }
+
+    // uint32 number = 2;
+
+
+    pub fn get_number(&self) -> u32 {
+        self.number
+    }
+    pub fn clear_number(&mut self) {
+        self.number = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_number(&mut self, v: u32) {
+        self.number = v;
+    }
+
+    // uint32 replica = 3;
+
+
+    pub fn get_replica(&self) -> u32 {
+        self.replica
+    }
+    pub fn clear_replica(&mut self) {
+        self.replica = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_replica(&mut self, v: u32) {
+        self.replica = v;
+    }
+}
+
+impl ::protobuf::Message for Ballot {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.epoch = tmp;
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.number = tmp;
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.replica = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.epoch != 0 {
+            my_size += ::protobuf::rt::value_size(1, self.epoch, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.number != 0 {
+            my_size += ::protobuf::rt::value_size(2, self.number, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.replica != 0 {
+            my_size += ::protobuf::rt::value_size(3, self.replica, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if self.epoch != 0 {
+            os.write_uint32(1, self.epoch)?;
+        }
+        if self.number != 0 {
+            os.write_uint32(2, self.number)?;
+        }
+        if self.replica != 0 {
+            os.write_uint32(3, self.replica)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> Ballot {
+        Ballot::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "epoch",
+                    |m: &Ballot| { &m.epoch },
+                    |m: &mut Ballot| { &mut m.epoch },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "number",
+                    |m: &Ballot| { &m.number },
+                    |m: &mut Ballot| { &mut m.number },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "replica",
+                    |m: &Ballot| { &m.replica },
+                    |m: &mut Ballot| { &mut m.replica },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<Ballot>(
+                    "Ballot",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static Ballot {
+        static mut instance: ::protobuf::lazy::Lazy<Ballot> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const Ballot,
+        };
+        unsafe {
+            instance.get(Ballot::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for Ballot {
+    fn clear(&mut self) {
+        self.epoch = 0;
+        self.number = 0;
+        self.replica = 0;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Ballot {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Ballot {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct PreparePayload {
+    // message fields
+    pub ballot: ::protobuf::SingularPtrField<Ballot>,
+    pub instance: ::protobuf::SingularPtrField<Instance>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a PreparePayload {
+    fn default() -> &'a PreparePayload {
+        <PreparePayload as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl PreparePayload {
+    pub fn new() -> PreparePayload {
+        ::std::default::Default::default()
+    }
+
+    // .epaxos.Ballot ballot = 1;
+
+
+    pub fn get_ballot(&self) -> &Ballot {
+        self.ballot.as_ref().unwrap_or_else(|| Ballot::default_instance())
+    }
+    pub fn clear_ballot(&mut self) {
+        self.ballot.clear();
+    }
+
+    pub fn has_ballot(&self) -> bool {
+        self.ballot.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_ballot(&mut self, v: Ballot) {
+        self.ballot = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_ballot(&mut self) -> &mut Ballot {
+        if self.ballot.is_none() {
+            self.ballot.set_default();
+        }
+        self.ballot.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_ballot(&mut self) -> Ballot {
+        self.ballot.take().unwrap_or_else(|| Ballot::new())
+    }
+
+    // .epaxos.Instance instance = 2;
+
+
+    pub fn get_instance(&self) -> &Instance {
+        self.instance.as_ref().unwrap_or_else(|| Instance::default_instance())
+    }
+    pub fn clear_instance(&mut self) {
+        self.instance.clear();
+    }
+
+    pub fn has_instance(&self) -> bool {
+        self.instance.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_instance(&mut self, v: Instance) {
+        self.instance = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_instance(&mut self) -> &mut Instance {
+        if self.instance.is_none() {
+            self.instance.set_default();
+        }
+        self.instance.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_instance(&mut self) -> Instance {
+        self.instance.take().unwrap_or_else(|| Instance::new())
+    }
+}
+
+impl ::protobuf::Message for PreparePayload {
+    fn is_initialized(&self) -> bool {
+        for v in &self.ballot {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.instance {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.ballot)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.instance)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(ref v) = self.ballot.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        if let Some(ref v) = self.instance.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if let Some(ref v) = self.ballot.as_ref() {
+            os.write_tag(1, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if let Some(ref v) = self.instance.as_ref() {
+            os.write_tag(2, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> PreparePayload {
+        PreparePayload::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Ballot>>(
+                    "ballot",
+                    |m: &PreparePayload| { &m.ballot },
+                    |m: &mut PreparePayload| { &mut m.ballot },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Instance>>(
+                    "instance",
+                    |m: &PreparePayload| { &m.instance },
+                    |m: &mut PreparePayload| { &mut m.instance },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<PreparePayload>(
+                    "PreparePayload",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static PreparePayload {
+        static mut instance: ::protobuf::lazy::Lazy<PreparePayload> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const PreparePayload,
+        };
+        unsafe {
+            instance.get(PreparePayload::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for PreparePayload {
+    fn clear(&mut self) {
+        self.ballot.clear();
+        self.instance.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for PreparePayload {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for PreparePayload {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct PrepareOKPayload {
+    // message fields
+    pub ok: bool,
+    pub ballot: ::protobuf::SingularPtrField<Ballot>,
+    pub replica: u32,
+    pub instance: ::protobuf::SingularPtrField<Instance>,
+    pub state: State,
+    pub payload: ::protobuf::SingularPtrField<Payload>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a PrepareOKPayload {
+    fn default() -> &'a PrepareOKPayload {
+        <PrepareOKPayload as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl PrepareOKPayload {
+    pub fn new() -> PrepareOKPayload {
+        ::std::default::Default::default()
+    }
+
+    // bool ok = 1;
+
+
+    pub fn get_ok(&self) -> bool {
+        self.ok
+    }
+    pub fn clear_ok(&mut self) {
+        self.ok = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_ok(&mut self, v: bool) {
+        self.ok = v;
+    }
+
+    // .epaxos.Ballot ballot = 2;
+
+
+    pub fn get_ballot(&self) -> &Ballot {
+        self.ballot.as_ref().unwrap_or_else(|| Ballot::default_instance())
+    }
+    pub fn clear_ballot(&mut self) {
+        self.ballot.clear();
+    }
+
+    pub fn has_ballot(&self) -> bool {
+        self.ballot.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_ballot(&mut self, v: Ballot) {
+        self.ballot = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_ballot(&mut self) -> &mut Ballot {
+        if self.ballot.is_none() {
+            self.ballot.set_default();
+        }
+        self.ballot.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_ballot(&mut self) -> Ballot {
+        self.ballot.take().unwrap_or_else(|| Ballot::new())
+    }
+
+    // uint32 replica = 3;
+
+
+    pub fn get_replica(&self) -> u32 {
+        self.replica
+    }
+    pub fn clear_replica(&mut self) {
+        self.replica = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_replica(&mut self, v: u32) {
+        self.replica = v;
+    }
+
+    // .epaxos.Instance instance = 4;
+
+
+    pub fn get_instance(&self) -> &Instance {
+        self.instance.as_ref().unwrap_or_else(|| Instance::default_instance())
+    }
+    pub fn clear_instance(&mut self) {
+        self.instance.clear();
+    }
+
+    pub fn has_instance(&self) -> bool {
+        self.instance.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_instance(&mut self, v: Instance) {
+        self.instance = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_instance(&mut self) -> &mut Instance {
+        if self.instance.is_none() {
+            self.instance.set_default();
+        }
+        self.instance.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_instance(&mut self) -> Instance {
+        self.instance.take().unwrap_or_else(|| Instance::new())
+    }
+
+    // .epaxos.State state = 5;
+
+
+    pub fn get_state(&self) -> State {
+        self.state
+    }
+    pub fn clear_state(&mut self) {
+        self.state = State::NONE;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_state(&mut self, v: State) {
+        self.state = v;
+    }
+
+    // .epaxos.Payload payload = 6;
+
+
+    pub fn get_payload(&self) -> &Payload {
+        self.payload.as_ref().unwrap_or_else(|| Payload::default_instance())
+    }
+    pub fn clear_payload(&mut self) {
+        self.payload.clear();
+    }
+
+    pub fn has_payload(&self) -> bool {
+        self.payload.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_payload(&mut self, v: Payload) {
+        self.payload = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_payload(&mut self) -> &mut Payload {
+        if self.payload.is_none() {
+            self.payload.set_default();
+        }
+        self.payload.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_payload(&mut self) -> Payload {
+        self.payload.take().unwrap_or_else(|| Payload::new())
+    }
+}
+
+impl ::protobuf::Message for PrepareOKPayload {
+    fn is_initialized(&self) -> bool {
+        for v in &self.ballot {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.instance {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.payload {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.ok = tmp;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.ballot)?;
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.replica = tmp;
+                },
+                4 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.instance)?;
+                },
+                5 => {
+                    ::protobuf::rt::read_proto3_enum_with_unknown_fields_into(wire_type, is, &mut self.state, 5, &mut self.unknown_fields)?
+                },
+                6 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.payload)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.ok != false {
+            my_size += 2;
+        }
+        if let Some(ref v) = self.ballot.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        if self.replica != 0 {
+            my_size += ::protobuf::rt::value_size(3, self.replica, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(ref v) = self.instance.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        if self.state != State::NONE {
+            my_size += ::protobuf::rt::enum_size(5, self.state);
+        }
+        if let Some(ref v) = self.payload.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if self.ok != false {
+            os.write_bool(1, self.ok)?;
+        }
+        if let Some(ref v) = self.ballot.as_ref() {
+            os.write_tag(2, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if self.replica != 0 {
+            os.write_uint32(3, self.replica)?;
+        }
+        if let Some(ref v) = self.instance.as_ref() {
+            os.write_tag(4, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if self.state != State::NONE {
+            os.write_enum(5, self.state.value())?;
+        }
+        if let Some(ref v) = self.payload.as_ref() {
+            os.write_tag(6, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> PrepareOKPayload {
+        PrepareOKPayload::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                    "ok",
+                    |m: &PrepareOKPayload| { &m.ok },
+                    |m: &mut PrepareOKPayload| { &mut m.ok },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Ballot>>(
+                    "ballot",
+                    |m: &PrepareOKPayload| { &m.ballot },
+                    |m: &mut PrepareOKPayload| { &mut m.ballot },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "replica",
+                    |m: &PrepareOKPayload| { &m.replica },
+                    |m: &mut PrepareOKPayload| { &mut m.replica },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Instance>>(
+                    "instance",
+                    |m: &PrepareOKPayload| { &m.instance },
+                    |m: &mut PrepareOKPayload| { &mut m.instance },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<State>>(
+                    "state",
+                    |m: &PrepareOKPayload| { &m.state },
+                    |m: &mut PrepareOKPayload| { &mut m.state },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Payload>>(
+                    "payload",
+                    |m: &PrepareOKPayload| { &m.payload },
+                    |m: &mut PrepareOKPayload| { &mut m.payload },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<PrepareOKPayload>(
+                    "PrepareOKPayload",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static PrepareOKPayload {
+        static mut instance: ::protobuf::lazy::Lazy<PrepareOKPayload> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const PrepareOKPayload,
+        };
+        unsafe {
+            instance.get(PrepareOKPayload::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for PrepareOKPayload {
+    fn clear(&mut self) {
+        self.ok = false;
+        self.ballot.clear();
+        self.replica = 0;
+        self.instance.clear();
+        self.state = State::NONE;
+        self.payload.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for PrepareOKPayload {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for PrepareOKPayload {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct WalRecord {
+    // message fields
+    pub state: State,
+    pub payload: ::protobuf::SingularPtrField<Payload>,
+    pub promise: ::protobuf::SingularPtrField<PreparePayload>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a WalRecord {
+    fn default() -> &'a WalRecord {
+        <WalRecord as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl WalRecord {
+    pub fn new() -> WalRecord {
+        ::std::default::Default::default()
+    }
+
+    // .epaxos.State state = 1;
+
+
+    pub fn get_state(&self) -> State {
+        self.state
+    }
+    pub fn clear_state(&mut self) {
+        self.state = State::NONE;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_state(&mut self, v: State) {
+        self.state = v;
+    }
+
+    // .epaxos.Payload payload = 2;
+
+
+    pub fn get_payload(&self) -> &Payload {
+        self.payload.as_ref().unwrap_or_else(|| Payload::default_instance())
+    }
+    pub fn clear_payload(&mut self) {
+        self.payload.clear();
+    }
+
+    pub fn has_payload(&self) -> bool {
+        self.payload.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_payload(&mut self, v: Payload) {
+        self.payload = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_payload(&mut self) -> &mut Payload {
+        if self.payload.is_none() {
+            self.payload.set_default();
+        }
+        self.payload.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_payload(&mut self) -> Payload {
+        self.payload.take().unwrap_or_else(|| Payload::new())
+    }
+
+    // .epaxos.PreparePayload promise = 3;
+
+
+    pub fn get_promise(&self) -> &PreparePayload {
+        self.promise.as_ref().unwrap_or_else(|| PreparePayload::default_instance())
+    }
+    pub fn clear_promise(&mut self) {
+        self.promise.clear();
+    }
+
+    pub fn has_promise(&self) -> bool {
+        self.promise.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_promise(&mut self, v: PreparePayload) {
+        self.promise = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_promise(&mut self) -> &mut PreparePayload {
+        if self.promise.is_none() {
+            self.promise.set_default();
+        }
+        self.promise.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_promise(&mut self) -> PreparePayload {
+        self.promise.take().unwrap_or_else(|| PreparePayload::new())
+    }
+}
+
+impl ::protobuf::Message for WalRecord {
+    fn is_initialized(&self) -> bool {
+        for v in &self.payload {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.promise {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_proto3_enum_with_unknown_fields_into(wire_type, is, &mut self.state, 1, &mut self.unknown_fields)?
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.payload)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.promise)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.state != State::NONE {
+            my_size += ::protobuf::rt::enum_size(1, self.state);
+        }
+        if let Some(ref v) = self.payload.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        if let Some(ref v) = self.promise.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if self.state != State::NONE {
+            os.write_enum(1, self.state.value())?;
+        }
+        if let Some(ref v) = self.payload.as_ref() {
+            os.write_tag(2, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if let Some(ref v) = self.promise.as_ref() {
+            os.write_tag(3, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> WalRecord {
+        WalRecord::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<State>>(
+                    "state",
+                    |m: &WalRecord| { &m.state },
+                    |m: &mut WalRecord| { &mut m.state },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Payload>>(
+                    "payload",
+                    |m: &WalRecord| { &m.payload },
+                    |m: &mut WalRecord| { &mut m.payload },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<PreparePayload>>(
+                    "promise",
+                    |m: &WalRecord| { &m.promise },
+                    |m: &mut WalRecord| { &mut m.promise },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<WalRecord>(
+                    "WalRecord",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static WalRecord {
+        static mut instance: ::protobuf::lazy::Lazy<WalRecord> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const WalRecord,
+        };
+        unsafe {
+            instance.get(WalRecord::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for WalRecord {
+    fn clear(&mut self) {
+        self.state = State::NONE;
+        self.payload.clear();
+        self.promise.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for WalRecord {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for WalRecord {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Watermarks {
+    // message fields
+    pub replica: u32,
+    pub executed: ::std::vec::Vec<u32>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a Watermarks {
+    fn default() -> &'a Watermarks {
+        <Watermarks as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Watermarks {
+    pub fn new() -> Watermarks {
+        ::std::default::Default::default()
+    }
+
+    // uint32 replica = 1;
+
+
+    pub fn get_replica(&self) -> u32 {
+        self.replica
+    }
+    pub fn clear_replica(&mut self) {
+        self.replica = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_replica(&mut self, v: u32) {
+        self.replica = v;
+    }
+
+    // repeated uint32 executed = 2;
+
+
+    pub fn get_executed(&self) -> &[u32] {
+        &self.executed
+    }
+    pub fn clear_executed(&mut self) {
+        self.executed.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_executed(&mut self, v: ::std::vec::Vec<u32>) {
+        self.executed = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_executed(&mut self) -> &mut ::std::vec::Vec<u32> {
+        &mut self.executed
+    }
+
+    // Take field
+    pub fn take_executed(&mut self) -> ::std::vec::Vec<u32> {
+        ::std::mem::replace(&mut self.executed, ::std::vec::Vec::new())
+    }
+}
+
+impl ::protobuf::Message for Watermarks {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.replica = tmp;
+                },
+                2 => {
+                    ::protobuf::rt::read_repeated_uint32_into(wire_type, is, &mut self.executed)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.replica != 0 {
+            my_size += ::protobuf::rt::value_size(1, self.replica, ::protobuf::wire_format::WireTypeVarint);
+        }
+        for value in &self.executed {
+            my_size += ::protobuf::rt::value_size(2, *value, ::protobuf::wire_format::WireTypeVarint);
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if self.replica != 0 {
+            os.write_uint32(1, self.replica)?;
+        }
+        for v in &self.executed {
+            os.write_uint32(2, *v)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> Watermarks {
+        Watermarks::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "replica",
+                    |m: &Watermarks| { &m.replica },
+                    |m: &mut Watermarks| { &mut m.replica },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_vec_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "executed",
+                    |m: &Watermarks| { &m.executed },
+                    |m: &mut Watermarks| { &mut m.executed },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<Watermarks>(
+                    "Watermarks",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static Watermarks {
+        static mut instance: ::protobuf::lazy::Lazy<Watermarks> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const Watermarks,
+        };
+        unsafe {
+            instance.get(Watermarks::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for Watermarks {
+    fn clear(&mut self) {
+        self.replica = 0;
+        self.executed.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Watermarks {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Watermarks {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct KeyValue {
+    // message fields
+    pub key: ::std::vec::Vec<u8>,
+    pub value: ::std::vec::Vec<u8>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a KeyValue {
+    fn default() -> &'a KeyValue {
+        <KeyValue as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl KeyValue {
+    pub fn new() -> KeyValue {
+        ::std::default::Default::default()
+    }
+
+    // bytes key = 1;
+
+
+    pub fn get_key(&self) -> &[u8] {
+        &self.key
+    }
+    pub fn clear_key(&mut self) {
+        self.key.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_key(&mut self, v: ::std::vec::Vec<u8>) {
+        self.key = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_key(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.key
+    }
+
+    // Take field
+    pub fn take_key(&mut self) -> ::std::vec::Vec<u8> {
+        ::std::mem::replace(&mut self.key, ::std::vec::Vec::new())
+    }
+
+    // bytes value = 2;
+
+
+    pub fn get_value(&self) -> &[u8] {
+        &self.value
+    }
+    pub fn clear_value(&mut self) {
+        self.value.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_value(&mut self, v: ::std::vec::Vec<u8>) {
+        self.value = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_value(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.value
+    }
+
+    // Take field
+    pub fn take_value(&mut self) -> ::std::vec::Vec<u8> {
+        ::std::mem::replace(&mut self.value, ::std::vec::Vec::new())
+    }
+}
+
+impl ::protobuf::Message for KeyValue {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.key)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.value)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.key.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(1, &self.key);
+        }
+        if !self.value.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(2, &self.value);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.key.is_empty() {
+            os.write_bytes(1, &self.key)?;
+        }
+        if !self.value.is_empty() {
+            os.write_bytes(2, &self.value)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> KeyValue {
+        KeyValue::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                    "key",
+                    |m: &KeyValue| { &m.key },
+                    |m: &mut KeyValue| { &mut m.key },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                    "value",
+                    |m: &KeyValue| { &m.value },
+                    |m: &mut KeyValue| { &mut m.value },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<KeyValue>(
+                    "KeyValue",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static KeyValue {
+        static mut instance: ::protobuf::lazy::Lazy<KeyValue> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const KeyValue,
+        };
+        unsafe {
+            instance.get(KeyValue::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for KeyValue {
+    fn clear(&mut self) {
+        self.key.clear();
+        self.value.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for KeyValue {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for KeyValue {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct KvStore {
+    // message fields
+    pub entries: ::protobuf::RepeatedField<KeyValue>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a KvStore {
+    fn default() -> &'a KvStore {
+        <KvStore as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl KvStore {
+    pub fn new() -> KvStore {
+        ::std::default::Default::default()
+    }
+
+    // repeated .epaxos.KeyValue entries = 1;
+
+
+    pub fn get_entries(&self) -> &[KeyValue] {
+        &self.entries
+    }
+    pub fn clear_entries(&mut self) {
+        self.entries.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_entries(&mut self, v: ::protobuf::RepeatedField<KeyValue>) {
+        self.entries = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_entries(&mut self) -> &mut ::protobuf::RepeatedField<KeyValue> {
+        &mut self.entries
+    }
+
+    // Take field
+    pub fn take_entries(&mut self) -> ::protobuf::RepeatedField<KeyValue> {
+        ::std::mem::replace(&mut self.entries, ::protobuf::RepeatedField::new())
+    }
+}
+
+impl ::protobuf::Message for KvStore {
+    fn is_initialized(&self) -> bool {
+        for v in &self.entries {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.entries)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in &self.entries {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        for v in &self.entries {
+            os.write_tag(1, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> KvStore {
+        KvStore::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<KeyValue>>(
+                    "entries",
+                    |m: &KvStore| { &m.entries },
+                    |m: &mut KvStore| { &mut m.entries },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<KvStore>(
+                    "KvStore",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static KvStore {
+        static mut instance: ::protobuf::lazy::Lazy<KvStore> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const KvStore,
+        };
+        unsafe {
+            instance.get(KvStore::new)
+        }
     }
+}
+
+impl ::protobuf::Clear for KvStore {
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for KvStore {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for KvStore {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Snapshot {
+    // message fields
+    pub state: ::std::vec::Vec<u8>,
+    pub watermarks: ::std::vec::Vec<u32>,
+    pub truncated: ::std::vec::Vec<u32>,
+    pub executed: ::protobuf::RepeatedField<Instance>,
+    pub epoch: u32,
+    pub members: ::protobuf::RepeatedField<ReplicaInfo>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a Snapshot {
+    fn default() -> &'a Snapshot {
+        <Snapshot as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Snapshot {
+    pub fn new() -> Snapshot {
+        ::std::default::Default::default()
+    }
+
+    // bytes state = 1;
+
+
+    pub fn get_state(&self) -> &[u8] {
+        &self.state
+    }
+    pub fn clear_state(&mut self) {
+        self.state.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_state(&mut self, v: ::std::vec::Vec<u8>) {
+        self.state = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_state(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.state
+    }
+
+    // Take field
+    pub fn take_state(&mut self) -> ::std::vec::Vec<u8> {
+        ::std::mem::replace(&mut self.state, ::std::vec::Vec::new())
+    }
+
+    // repeated uint32 watermarks = 2;
+
+
+    pub fn get_watermarks(&self) -> &[u32] {
+        &self.watermarks
+    }
+    pub fn clear_watermarks(&mut self) {
+        self.watermarks.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_watermarks(&mut self, v: ::std::vec::Vec<u32>) {
+        self.watermarks = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_watermarks(&mut self) -> &mut ::std::vec::Vec<u32> {
+        &mut self.watermarks
+    }
+
+    // Take field
+    pub fn take_watermarks(&mut self) -> ::std::vec::Vec<u32> {
+        ::std::mem::replace(&mut self.watermarks, ::std::vec::Vec::new())
+    }
+
+    // repeated uint32 truncated = 3;
+
+
+    pub fn get_truncated(&self) -> &[u32] {
+        &self.truncated
+    }
+    pub fn clear_truncated(&mut self) {
+        self.truncated.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_truncated(&mut self, v: ::std::vec::Vec<u32>) {
+        self.truncated = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_truncated(&mut self) -> &mut ::std::vec::Vec<u32> {
+        &mut self.truncated
+    }
+
+    // Take field
+    pub fn take_truncated(&mut self) -> ::std::vec::Vec<u32> {
+        ::std::mem::replace(&mut self.truncated, ::std::vec::Vec::new())
+    }
+
+    // repeated .epaxos.Instance executed = 4;
+
+
+    pub fn get_executed(&self) -> &[Instance] {
+        &self.executed
+    }
+    pub fn clear_executed(&mut self) {
+        self.executed.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_executed(&mut self, v: ::protobuf::RepeatedField<Instance>) {
+        self.executed = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_executed(&mut self) -> &mut ::protobuf::RepeatedField<Instance> {
+        &mut self.executed
+    }
+
+    // Take field
+    pub fn take_executed(&mut self) -> ::protobuf::RepeatedField<Instance> {
+        ::std::mem::replace(&mut self.executed, ::protobuf::RepeatedField::new())
+    }
+
+    // uint32 epoch = 5;
+
+
+    pub fn get_epoch(&self) -> u32 {
+        self.epoch
+    }
+    pub fn clear_epoch(&mut self) {
+        self.epoch = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_epoch(&mut self, v: u32) {
+        self.epoch = v;
+    }
+
+    // repeated .epaxos.ReplicaInfo members = 6;
+
+
+    pub fn get_members(&self) -> &[ReplicaInfo] {
+        &self.members
+    }
+    pub fn clear_members(&mut self) {
+        self.members.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_members(&mut self, v: ::protobuf::RepeatedField<ReplicaInfo>) {
+        self.members = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_members(&mut self) -> &mut ::protobuf::RepeatedField<ReplicaInfo> {
+        &mut self.members
+    }
+
+    // Take field
+    pub fn take_members(&mut self) -> ::protobuf::RepeatedField<ReplicaInfo> {
+        ::std::mem::replace(&mut self.members, ::protobuf::RepeatedField::new())
+    }
+}
+
+impl ::protobuf::Message for Snapshot {
+    fn is_initialized(&self) -> bool {
+        for v in &self.executed {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.members {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.state)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_repeated_uint32_into(wire_type, is, &mut self.watermarks)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_repeated_uint32_into(wire_type, is, &mut self.truncated)?;
+                },
+                4 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.executed)?;
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.epoch = tmp;
+                },
+                6 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.members)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.state.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(1, &self.state);
+        }
+        for value in &self.watermarks {
+            my_size += ::protobuf::rt::value_size(2, *value, ::protobuf::wire_format::WireTypeVarint);
+        };
+        for value in &self.truncated {
+            my_size += ::protobuf::rt::value_size(3, *value, ::protobuf::wire_format::WireTypeVarint);
+        };
+        for value in &self.executed {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        if self.epoch != 0 {
+            my_size += ::protobuf::rt::value_size(5, self.epoch, ::protobuf::wire_format::WireTypeVarint);
+        }
+        for value in &self.members {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.state.is_empty() {
+            os.write_bytes(1, &self.state)?;
+        }
+        for v in &self.watermarks {
+            os.write_uint32(2, *v)?;
+        };
+        for v in &self.truncated {
+            os.write_uint32(3, *v)?;
+        };
+        for v in &self.executed {
+            os.write_tag(4, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        if self.epoch != 0 {
+            os.write_uint32(5, self.epoch)?;
+        }
+        for v in &self.members {
+            os.write_tag(6, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> Snapshot {
+        Snapshot::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                    "state",
+                    |m: &Snapshot| { &m.state },
+                    |m: &mut Snapshot| { &mut m.state },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_vec_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "watermarks",
+                    |m: &Snapshot| { &m.watermarks },
+                    |m: &mut Snapshot| { &mut m.watermarks },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_vec_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "truncated",
+                    |m: &Snapshot| { &m.truncated },
+                    |m: &mut Snapshot| { &mut m.truncated },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Instance>>(
+                    "executed",
+                    |m: &Snapshot| { &m.executed },
+                    |m: &mut Snapshot| { &mut m.executed },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "epoch",
+                    |m: &Snapshot| { &m.epoch },
+                    |m: &mut Snapshot| { &mut m.epoch },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<ReplicaInfo>>(
+                    "members",
+                    |m: &Snapshot| { &m.members },
+                    |m: &mut Snapshot| { &mut m.members },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<Snapshot>(
+                    "Snapshot",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static Snapshot {
+        static mut instance: ::protobuf::lazy::Lazy<Snapshot> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const Snapshot,
+        };
+        unsafe {
+            instance.get(Snapshot::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for Snapshot {
+    fn clear(&mut self) {
+        self.state.clear();
+        self.watermarks.clear();
+        self.truncated.clear();
+        self.executed.clear();
+        self.epoch = 0;
+        self.members.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Snapshot {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Snapshot {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
 
-    // Mutable pointer to the field.
-    // If field is not initialized, it is initialized with default value first.
-    pub fn mut_write_req(&mut self) -> &mut WriteRequest {
-        if self.write_req.is_none() {
-            self.write_req.set_default();
-        }
-        self.write_req.as_mut().unwrap()
+#[derive(PartialEq,Clone,Default)]
+pub struct CatchUpRequest {
+    // message fields
+    pub replica: u32,
+    pub executed: ::std::vec::Vec<u32>,
+    pub committed: ::protobuf::RepeatedField<Instance>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a CatchUpRequest {
+    fn default() -> &'a CatchUpRequest {
+        <CatchUpRequest as ::protobuf::Message>::default_instance()
     }
+}
 
-    // Take field
-    pub fn take_write_req(&mut self) -> WriteRequest {
-        self.write_req.take().unwrap_or_else(|| WriteRequest::new())
+impl CatchUpRequest {
+    pub fn new() -> CatchUpRequest {
+        ::std::default::Default::default()
     }
 
-    // uint32 seq = 3;
+    // uint32 replica = 1;
 
 
-    pub fn get_seq(&self) -> u32 {
-        self.seq
+    pub fn get_replica(&self) -> u32 {
+        self.replica
     }
-    pub fn clear_seq(&mut self) {
-        self.seq = 0;
+    pub fn clear_replica(&mut self) {
+        self.replica = 0;
     }
 
     // Param is passed by value, moved
-    pub fn set_seq(&mut self, v: u32) {
-        self.seq = v;
+    pub fn set_replica(&mut self, v: u32) {
+        self.replica = v;
     }
 
-    // repeated .epaxos.Instance deps = 4;
+    // repeated uint32 executed = 2;
 
 
-    pub fn get_deps(&self) -> &[Instance] {
-        &self.deps
+    pub fn get_executed(&self) -> &[u32] {
+        &self.executed
     }
-    pub fn clear_deps(&mut self) {
-        self.deps.clear();
+    pub fn clear_executed(&mut self) {
+        self.executed.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_deps(&mut self, v: ::protobuf::RepeatedField<Instance>) {
-        self.deps = v;
+    pub fn set_executed(&mut self, v: ::std::vec::Vec<u32>) {
+        self.executed = v;
     }
 
     // Mutable pointer to the field.
-    pub fn mut_deps(&mut self) -> &mut ::protobuf::RepeatedField<Instance> {
-        &mut self.deps
+    pub fn mut_executed(&mut self) -> &mut ::std::vec::Vec<u32> {
+        &mut self.executed
     }
 
     // Take field
-    pub fn take_deps(&mut self) -> ::protobuf::RepeatedField<Instance> {
-        ::std::mem::replace(&mut self.deps, ::protobuf::RepeatedField::new())
+    pub fn take_executed(&mut self) -> ::std::vec::Vec<u32> {
+        ::std::mem::replace(&mut self.executed, ::std::vec::Vec::new())
     }
 
-    // .epaxos.Instance instance = 5;
+    // repeated .epaxos.Instance committed = 3;
 
 
-    pub fn get_instance(&self) -> &Instance {
-        self.instance.as_ref().unwrap_or_else(|| Instance::default_instance())
-    }
-    pub fn clear_instance(&mut self) {
-        self.instance.clear();
+    pub fn get_committed(&self) -> &[Instance] {
+        &self.committed
     }
-
-    pub fn has_instance(&self) -> bool {
-        self.instance.is_some()
+    pub fn clear_committed(&mut self) {
+        self.committed.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_instance(&mut self, v: Instance) {
-        self.instance = ::protobuf::SingularPtrField::some(v);
+    pub fn set_committed(&mut self, v: ::protobuf::RepeatedField<Instance>) {
+        self.committed = v;
     }
 
     // Mutable pointer to the field.
-    // If field is not initialized, it is initialized with default value first.
-    pub fn mut_instance(&mut self) -> &mut Instance {
-        if self.instance.is_none() {
-            self.instance.set_default();
-        }
-        self.instance.as_mut().unwrap()
+    pub fn mut_committed(&mut self) -> &mut ::protobuf::RepeatedField<Instance> {
+        &mut self.committed
     }
 
     // Take field
-    pub fn take_instance(&mut self) -> Instance {
-        self.instance.take().unwrap_or_else(|| Instance::new())
+    pub fn take_committed(&mut self) -> ::protobuf::RepeatedField<Instance> {
+        ::std::mem::replace(&mut self.committed, ::protobuf::RepeatedField::new())
     }
 }
 
-impl ::protobuf::Message for Payload {
+impl ::protobuf::Message for CatchUpRequest {
     fn is_initialized(&self) -> bool {
-        for v in &self.write_req {
-            if !v.is_initialized() {
-                return false;
-            }
-        };
-        for v in &self.deps {
-            if !v.is_initialized() {
-                return false;
-            }
-        };
-        for v in &self.instance {
+        for v in &self.committed {
             if !v.is_initialized() {
                 return false;
             }
@@ -1074,21 +5667,18 @@ impl ::protobuf::Message for Payload {
         while !is.eof()? {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
-                2 => {
-                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.write_req)?;
-                },
-                3 => {
+                1 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
                     let tmp = is.read_uint32()?;
-                    self.seq = tmp;
+                    self.replica = tmp;
                 },
-                4 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.deps)?;
+                2 => {
+                    ::protobuf::rt::read_repeated_uint32_into(wire_type, is, &mut self.executed)?;
                 },
-                5 => {
-                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.instance)?;
+                3 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.committed)?;
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -1102,45 +5692,33 @@ impl ::protobuf::Message for Payload {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        if let Some(ref v) = self.write_req.as_ref() {
-            let len = v.compute_size();
-            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
-        }
-        if self.seq != 0 {
-            my_size += ::protobuf::rt::value_size(3, self.seq, ::protobuf::wire_format::WireTypeVarint);
+        if self.replica != 0 {
+            my_size += ::protobuf::rt::value_size(1, self.replica, ::protobuf::wire_format::WireTypeVarint);
         }
-        for value in &self.deps {
+        for value in &self.executed {
+            my_size += ::protobuf::rt::value_size(2, *value, ::protobuf::wire_format::WireTypeVarint);
+        };
+        for value in &self.committed {
             let len = value.compute_size();
             my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         };
-        if let Some(ref v) = self.instance.as_ref() {
-            let len = v.compute_size();
-            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
-        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        if let Some(ref v) = self.write_req.as_ref() {
-            os.write_tag(2, ::protobuf::wire_format::WireTypeLengthDelimited)?;
-            os.write_raw_varint32(v.get_cached_size())?;
-            v.write_to_with_cached_sizes(os)?;
-        }
-        if self.seq != 0 {
-            os.write_uint32(3, self.seq)?;
+        if self.replica != 0 {
+            os.write_uint32(1, self.replica)?;
         }
-        for v in &self.deps {
-            os.write_tag(4, ::protobuf::wire_format::WireTypeLengthDelimited)?;
-            os.write_raw_varint32(v.get_cached_size())?;
-            v.write_to_with_cached_sizes(os)?;
+        for v in &self.executed {
+            os.write_uint32(2, *v)?;
         };
-        if let Some(ref v) = self.instance.as_ref() {
-            os.write_tag(5, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+        for v in &self.committed {
+            os.write_tag(3, ::protobuf::wire_format::WireTypeLengthDelimited)?;
             os.write_raw_varint32(v.get_cached_size())?;
             v.write_to_with_cached_sizes(os)?;
-        }
+        };
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -1171,8 +5749,8 @@ impl ::protobuf::Message for Payload {
         Self::descriptor_static()
     }
 
-    fn new() -> Payload {
-        Payload::new()
+    fn new() -> CatchUpRequest {
+        CatchUpRequest::new()
     }
 
     fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
@@ -1183,28 +5761,23 @@ impl ::protobuf::Message for Payload {
         unsafe {
             descriptor.get(|| {
                 let mut fields = ::std::vec::Vec::new();
-                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<WriteRequest>>(
-                    "write_req",
-                    |m: &Payload| { &m.write_req },
-                    |m: &mut Payload| { &mut m.write_req },
-                ));
                 fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
-                    "seq",
-                    |m: &Payload| { &m.seq },
-                    |m: &mut Payload| { &mut m.seq },
+                    "replica",
+                    |m: &CatchUpRequest| { &m.replica },
+                    |m: &mut CatchUpRequest| { &mut m.replica },
                 ));
-                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Instance>>(
-                    "deps",
-                    |m: &Payload| { &m.deps },
-                    |m: &mut Payload| { &mut m.deps },
+                fields.push(::protobuf::reflect::accessor::make_vec_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "executed",
+                    |m: &CatchUpRequest| { &m.executed },
+                    |m: &mut CatchUpRequest| { &mut m.executed },
                 ));
-                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Instance>>(
-                    "instance",
-                    |m: &Payload| { &m.instance },
-                    |m: &mut Payload| { &mut m.instance },
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Instance>>(
+                    "committed",
+                    |m: &CatchUpRequest| { &m.committed },
+                    |m: &mut CatchUpRequest| { &mut m.committed },
                 ));
-                ::protobuf::reflect::MessageDescriptor::new::<Payload>(
-                    "Payload",
+                ::protobuf::reflect::MessageDescriptor::new::<CatchUpRequest>(
+                    "CatchUpRequest",
                     fields,
                     file_descriptor_proto()
                 )
@@ -1212,135 +5785,126 @@ impl ::protobuf::Message for Payload {
         }
     }
 
-    fn default_instance() -> &'static Payload {
-        static mut instance: ::protobuf::lazy::Lazy<Payload> = ::protobuf::lazy::Lazy {
+    fn default_instance() -> &'static CatchUpRequest {
+        static mut instance: ::protobuf::lazy::Lazy<CatchUpRequest> = ::protobuf::lazy::Lazy {
             lock: ::protobuf::lazy::ONCE_INIT,
-            ptr: 0 as *const Payload,
+            ptr: 0 as *const CatchUpRequest,
         };
         unsafe {
-            instance.get(Payload::new)
+            instance.get(CatchUpRequest::new)
         }
     }
 }
 
-impl ::protobuf::Clear for Payload {
+impl ::protobuf::Clear for CatchUpRequest {
     fn clear(&mut self) {
-        self.write_req.clear();
-        self.seq = 0;
-        self.deps.clear();
-        self.instance.clear();
+        self.replica = 0;
+        self.executed.clear();
+        self.committed.clear();
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::fmt::Debug for Payload {
+impl ::std::fmt::Debug for CatchUpRequest {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for Payload {
+impl ::protobuf::reflect::ProtobufValue for CatchUpRequest {
     fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
         ::protobuf::reflect::ProtobufValueRef::Message(self)
     }
 }
 
 #[derive(PartialEq,Clone,Default)]
-pub struct AcceptOKPayload {
+pub struct CatchUpReply {
     // message fields
-    pub command: ::protobuf::SingularPtrField<WriteRequest>,
-    pub instance: ::protobuf::SingularPtrField<Instance>,
+    pub committed: ::protobuf::RepeatedField<Payload>,
+    pub snapshot: ::protobuf::SingularPtrField<Snapshot>,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
 }
 
-impl<'a> ::std::default::Default for &'a AcceptOKPayload {
-    fn default() -> &'a AcceptOKPayload {
-        <AcceptOKPayload as ::protobuf::Message>::default_instance()
+impl<'a> ::std::default::Default for &'a CatchUpReply {
+    fn default() -> &'a CatchUpReply {
+        <CatchUpReply as ::protobuf::Message>::default_instance()
     }
 }
 
-impl AcceptOKPayload {
-    pub fn new() -> AcceptOKPayload {
+impl CatchUpReply {
+    pub fn new() -> CatchUpReply {
         ::std::default::Default::default()
     }
 
-    // .epaxos.WriteRequest command = 1;
+    // repeated .epaxos.Payload committed = 1;
 
 
-    pub fn get_command(&self) -> &WriteRequest {
-        self.command.as_ref().unwrap_or_else(|| WriteRequest::default_instance())
-    }
-    pub fn clear_command(&mut self) {
-        self.command.clear();
+    pub fn get_committed(&self) -> &[Payload] {
+        &self.committed
     }
-
-    pub fn has_command(&self) -> bool {
-        self.command.is_some()
+    pub fn clear_committed(&mut self) {
+        self.committed.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_command(&mut self, v: WriteRequest) {
-        self.command = ::protobuf::SingularPtrField::some(v);
+    pub fn set_committed(&mut self, v: ::protobuf::RepeatedField<Payload>) {
+        self.committed = v;
     }
 
     // Mutable pointer to the field.
-    // If field is not initialized, it is initialized with default value first.
-    pub fn mut_command(&mut self) -> &mut WriteRequest {
-        if self.command.is_none() {
-            self.command.set_default();
-        }
-        self.command.as_mut().unwrap()
+    pub fn mut_committed(&mut self) -> &mut ::protobuf::RepeatedField<Payload> {
+        &mut self.committed
     }
 
     // Take field
-    pub fn take_command(&mut self) -> WriteRequest {
-        self.command.take().unwrap_or_else(|| WriteRequest::new())
+    pub fn take_committed(&mut self) -> ::protobuf::RepeatedField<Payload> {
+        ::std::mem::replace(&mut self.committed, ::protobuf::RepeatedField::new())
     }
 
-    // .epaxos.Instance instance = 4;
+    // .epaxos.Snapshot snapshot = 2;
 
 
-    pub fn get_instance(&self) -> &Instance {
-        self.instance.as_ref().unwrap_or_else(|| Instance::default_instance())
+    pub fn get_snapshot(&self) -> &Snapshot {
+        self.snapshot.as_ref().unwrap_or_else(|| Snapshot::default_instance())
     }
-    pub fn clear_instance(&mut self) {
-        self.instance.clear();
+    pub fn clear_snapshot(&mut self) {
+        self.snapshot.clear();
     }
 
-    pub fn has_instance(&self) -> bool {
-        self.instance.is_some()
+    pub fn has_snapshot(&self) -> bool {
+        self.snapshot.is_some()
     }
 
     // Param is passed by value, moved
-    pub fn set_instance(&mut self, v: Instance) {
-        self.instance = ::protobuf::SingularPtrField::some(v);
+    pub fn set_snapshot(&mut self, v: Snapshot) {
+        self.snapshot = ::protobuf::SingularPtrField::some(v);
     }
 
     // Mutable pointer to the field.
     // If field is not initialized, it is initialized with default value first.
-    pub fn mut_instance(&mut self) -> &mut Instance {
-        if self.instance.is_none() {
-            self.instance.set_default();
+    pub fn mut_snapshot(&mut self) -> &mut Snapshot {
+        if self.snapshot.is_none() {
+            self.snapshot.set_default();
         }
-        self.instance.as_mut().unwrap()
+        self.snapshot.as_mut().unwrap()
     }
 
     // Take field
-    pub fn take_instance(&mut self) -> Instance {
-        self.instance.take().unwrap_or_else(|| Instance::new())
+    pub fn take_snapshot(&mut self) -> Snapshot {
+        self.snapshot.take().unwrap_or_else(|| Snapshot::new())
     }
 }
 
-impl ::protobuf::Message for AcceptOKPayload {
+impl ::protobuf::Message for CatchUpReply {
     fn is_initialized(&self) -> bool {
-        for v in &self.command {
+        for v in &self.committed {
             if !v.is_initialized() {
                 return false;
             }
         };
-        for v in &self.instance {
+        for v in &self.snapshot {
             if !v.is_initialized() {
                 return false;
             }
@@ -1353,10 +5917,10 @@ impl ::protobuf::Message for AcceptOKPayload {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.command)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.committed)?;
                 },
-                4 => {
-                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.instance)?;
+                2 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.snapshot)?;
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -1370,11 +5934,11 @@ impl ::protobuf::Message for AcceptOKPayload {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        if let Some(ref v) = self.command.as_ref() {
-            let len = v.compute_size();
+        for value in &self.committed {
+            let len = value.compute_size();
             my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
-        }
-        if let Some(ref v) = self.instance.as_ref() {
+        };
+        if let Some(ref v) = self.snapshot.as_ref() {
             let len = v.compute_size();
             my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         }
@@ -1384,13 +5948,13 @@ impl ::protobuf::Message for AcceptOKPayload {
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        if let Some(ref v) = self.command.as_ref() {
+        for v in &self.committed {
             os.write_tag(1, ::protobuf::wire_format::WireTypeLengthDelimited)?;
             os.write_raw_varint32(v.get_cached_size())?;
             v.write_to_with_cached_sizes(os)?;
-        }
-        if let Some(ref v) = self.instance.as_ref() {
-            os.write_tag(4, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+        };
+        if let Some(ref v) = self.snapshot.as_ref() {
+            os.write_tag(2, ::protobuf::wire_format::WireTypeLengthDelimited)?;
             os.write_raw_varint32(v.get_cached_size())?;
             v.write_to_with_cached_sizes(os)?;
         }
@@ -1424,8 +5988,8 @@ impl ::protobuf::Message for AcceptOKPayload {
         Self::descriptor_static()
     }
 
-    fn new() -> AcceptOKPayload {
-        AcceptOKPayload::new()
+    fn new() -> CatchUpReply {
+        CatchUpReply::new()
     }
 
     fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
@@ -1436,18 +6000,18 @@ impl ::protobuf::Message for AcceptOKPayload {
         unsafe {
             descriptor.get(|| {
                 let mut fields = ::std::vec::Vec::new();
-                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<WriteRequest>>(
-                    "command",
-                    |m: &AcceptOKPayload| { &m.command },
-                    |m: &mut AcceptOKPayload| { &mut m.command },
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Payload>>(
+                    "committed",
+                    |m: &CatchUpReply| { &m.committed },
+                    |m: &mut CatchUpReply| { &mut m.committed },
                 ));
-                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Instance>>(
-                    "instance",
-                    |m: &AcceptOKPayload| { &m.instance },
-                    |m: &mut AcceptOKPayload| { &mut m.instance },
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Snapshot>>(
+                    "snapshot",
+                    |m: &CatchUpReply| { &m.snapshot },
+                    |m: &mut CatchUpReply| { &mut m.snapshot },
                 ));
-                ::protobuf::reflect::MessageDescriptor::new::<AcceptOKPayload>(
-                    "AcceptOKPayload",
+                ::protobuf::reflect::MessageDescriptor::new::<CatchUpReply>(
+                    "CatchUpReply",
                     fields,
                     file_descriptor_proto()
                 )
@@ -1455,32 +6019,32 @@ impl ::protobuf::Message for AcceptOKPayload {
         }
     }
 
-    fn default_instance() -> &'static AcceptOKPayload {
-        static mut instance: ::protobuf::lazy::Lazy<AcceptOKPayload> = ::protobuf::lazy::Lazy {
+    fn default_instance() -> &'static CatchUpReply {
+        static mut instance: ::protobuf::lazy::Lazy<CatchUpReply> = ::protobuf::lazy::Lazy {
             lock: ::protobuf::lazy::ONCE_INIT,
-            ptr: 0 as *const AcceptOKPayload,
+            ptr: 0 as *const CatchUpReply,
         };
         unsafe {
-            instance.get(AcceptOKPayload::new)
+            instance.get(CatchUpReply::new)
         }
     }
 }
 
-impl ::protobuf::Clear for AcceptOKPayload {
+impl ::protobuf::Clear for CatchUpReply {
     fn clear(&mut self) {
-        self.command.clear();
-        self.instance.clear();
+        self.committed.clear();
+        self.snapshot.clear();
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::fmt::Debug for AcceptOKPayload {
+impl ::std::fmt::Debug for CatchUpReply {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for AcceptOKPayload {
+impl ::protobuf::reflect::ProtobufValue for CatchUpReply {
     fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
         ::protobuf::reflect::ProtobufValueRef::Message(self)
     }
@@ -1612,26 +6176,208 @@ impl ::protobuf::reflect::ProtobufValue for Empty {
     }
 }
 
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub enum MembershipChange {
+    ADD = 0,
+    REMOVE = 1,
+}
+
+impl ::protobuf::ProtobufEnum for MembershipChange {
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<MembershipChange> {
+        match value {
+            0 => ::std::option::Option::Some(MembershipChange::ADD),
+            1 => ::std::option::Option::Some(MembershipChange::REMOVE),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn values() -> &'static [Self] {
+        static values: &'static [MembershipChange] = &[
+            MembershipChange::ADD,
+            MembershipChange::REMOVE,
+        ];
+        values
+    }
+
+    fn enum_descriptor_static() -> &'static ::protobuf::reflect::EnumDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::EnumDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::EnumDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                ::protobuf::reflect::EnumDescriptor::new("MembershipChange", file_descriptor_proto())
+            })
+        }
+    }
+}
+
+impl ::std::marker::Copy for MembershipChange {
+}
+
+impl ::std::default::Default for MembershipChange {
+    fn default() -> Self {
+        MembershipChange::ADD
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for MembershipChange {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Enum(self.descriptor())
+    }
+}
+
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub enum State {
+    NONE = 0,
+    PRE_ACCEPTED = 1,
+    ACCEPTED = 2,
+    COMMITTED = 3,
+}
+
+impl ::protobuf::ProtobufEnum for State {
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<State> {
+        match value {
+            0 => ::std::option::Option::Some(State::NONE),
+            1 => ::std::option::Option::Some(State::PRE_ACCEPTED),
+            2 => ::std::option::Option::Some(State::ACCEPTED),
+            3 => ::std::option::Option::Some(State::COMMITTED),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn values() -> &'static [Self] {
+        static values: &'static [State] = &[
+            State::NONE,
+            State::PRE_ACCEPTED,
+            State::ACCEPTED,
+            State::COMMITTED,
+        ];
+        values
+    }
+
+    fn enum_descriptor_static() -> &'static ::protobuf::reflect::EnumDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::EnumDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::EnumDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                ::protobuf::reflect::EnumDescriptor::new("State", file_descriptor_proto())
+            })
+        }
+    }
+}
+
+impl ::std::marker::Copy for State {
+}
+
+impl ::std::default::Default for State {
+    fn default() -> Self {
+        State::NONE
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for State {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Enum(self.descriptor())
+    }
+}
+
 static file_descriptor_proto_data: &'static [u8] = b"\
     \n\x0cepaxos.proto\x12\x06epaxos\"6\n\x0cWriteRequest\x12\x10\n\x03key\
-    \x18\x01\x20\x01(\tR\x03key\x12\x14\n\x05value\x18\x02\x20\x01(\x05R\x05\
-    value\"'\n\rWriteResponse\x12\x16\n\x06commit\x18\x01\x20\x01(\x08R\x06c\
-    ommit\"\x1f\n\x0bReadRequest\x12\x10\n\x03key\x18\x01\x20\x01(\tR\x03key\
-    \"$\n\x0cReadResponse\x12\x14\n\x05value\x18\x01\x20\x01(\x05R\x05value\
-    \"8\n\x08Instance\x12\x18\n\x07replica\x18\x01\x20\x01(\rR\x07replica\
-    \x12\x12\n\x04slot\x18\x02\x20\x01(\rR\x04slot\"\xa2\x01\n\x07Payload\
-    \x121\n\twrite_req\x18\x02\x20\x01(\x0b2\x14.epaxos.WriteRequestR\x08wri\
-    teReq\x12\x10\n\x03seq\x18\x03\x20\x01(\rR\x03seq\x12$\n\x04deps\x18\x04\
-    \x20\x03(\x0b2\x10.epaxos.InstanceR\x04deps\x12,\n\x08instance\x18\x05\
-    \x20\x01(\x0b2\x10.epaxos.InstanceR\x08instance\"o\n\x0fAcceptOKPayload\
-    \x12.\n\x07command\x18\x01\x20\x01(\x0b2\x14.epaxos.WriteRequestR\x07com\
-    mand\x12,\n\x08instance\x18\x04\x20\x01(\x0b2\x10.epaxos.InstanceR\x08in\
-    stance\"\x07\n\x05Empty2\x86\x02\n\rEpaxosService\x12.\n\npre_accept\x12\
-    \x0f.epaxos.Payload\x1a\x0f.epaxos.Payload\x122\n\x06accept\x12\x0f.epax\
-    os.Payload\x1a\x17.epaxos.AcceptOKPayload\x12(\n\x06commit\x12\x0f.epaxo\
-    s.Payload\x1a\r.epaxos.Empty\x124\n\x05write\x12\x14.epaxos.WriteRequest\
-    \x1a\x15.epaxos.WriteResponse\x121\n\x04read\x12\x13.epaxos.ReadRequest\
-    \x1a\x14.epaxos.ReadResponseb\x06proto3\
+    \x18\x01\x20\x01(\x0cR\x03key\x12\x14\n\x05value\x18\x02\x20\x01(\x0cR\
+    \x05value\"'\n\rWriteResponse\x12\x16\n\x06commit\x18\x01\x20\x01(\x08R\
+    \x06commit\"C\n\x0bReadRequest\x12\x10\n\x03key\x18\x01\x20\x01(\x0cR\
+    \x03key\x12\"\n\x0clinearizable\x18\x02\x20\x01(\x08R\x0clinearizable\":\
+    \n\x0cReadResponse\x12\x14\n\x05value\x18\x01\x20\x01(\x0cR\x05value\x12\
+    \x14\n\x05found\x18\x02\x20\x01(\x08R\x05found\"\xae\x02\n\x07Command\
+    \x12\"\n\x02op\x18\x01\x20\x01(\x0e2\x12.epaxos.Command.OpR\x02op\x12\
+    \x10\n\x03key\x18\x02\x20\x01(\x0cR\x03key\x12\x14\n\x05value\x18\x03\
+    \x20\x01(\x0cR\x05value\x12\x14\n\x05delta\x18\x04\x20\x01(\x12R\x05delt\
+    a\x12%\n\x0eexpected_found\x18\x05\x20\x01(\x08R\rexpectedFound\x12\x1a\
+    \n\x08expected\x18\x06\x20\x01(\x0cR\x08expected\x12+\n\x08commands\x18\
+    \x07\x20\x03(\x0b2\x0f.epaxos.CommandR\x08commands\"Q\n\x02Op\x12\x07\n\
+    \x03PUT\x10\0\x12\n\n\x06DELETE\x10\x01\x12\r\n\tINCREMENT\x10\x02\x12\n\
+    \n\x06APPEND\x10\x03\x12\x07\n\x03CAS\x10\x04\x12\x07\n\x03GET\x10\x05\
+    \x12\t\n\x05MULTI\x10\x06\"|\n\rCommandResult\x12\x0e\n\x02ok\x18\x01\
+    \x20\x01(\x08R\x02ok\x12\x14\n\x05found\x18\x02\x20\x01(\x08R\x05found\
+    \x12\x14\n\x05value\x18\x03\x20\x01(\x0cR\x05value\x12/\n\x07results\x18\
+    \x04\x20\x03(\x0b2\x15.epaxos.CommandResultR\x07results\"N\n\x08Instance\
+    \x12\x18\n\x07replica\x18\x01\x20\x01(\rR\x07replica\x12\x12\n\x04slot\
+    \x18\x02\x20\x01(\rR\x04slot\x12\x14\n\x05epoch\x18\x03\x20\x01(\rR\x05e\
+    poch\"u\n\x0bReplicaInfo\x12\x0e\n\x02id\x18\x01\x20\x01(\rR\x02id\x12\
+    \x12\n\x04host\x18\x02\x20\x01(\tR\x04host\x12\x12\n\x04port\x18\x03\x20\
+    \x01(\rR\x04port\x12\x16\n\x06region\x18\x04\x20\x01(\tR\x06region\x12\
+    \x16\n\x06quorum\x18\x05\x20\x03(\rR\x06quorum\"r\n\x0fReconfiguration\
+    \x120\n\x06change\x18\x01\x20\x01(\x0e2\x18.epaxos.MembershipChangeR\x06\
+    change\x12-\n\x07replica\x18\x02\x20\x01(\x0b2\x13.epaxos.ReplicaInfoR\
+    \x07replica\"\x80\x02\n\x07Payload\x12\x10\n\x03seq\x18\x03\x20\x01(\rR\
+    \x03seq\x12$\n\x04deps\x18\x04\x20\x03(\x0b2\x10.epaxos.InstanceR\x04dep\
+    s\x12,\n\x08instance\x18\x05\x20\x01(\x0b2\x10.epaxos.InstanceR\x08insta\
+    nce\x12\x12\n\x04noop\x18\x06\x20\x01(\x08R\x04noop\x12&\n\x06ballot\x18\
+    \x07\x20\x01(\x0b2\x0e.epaxos.BallotR\x06ballot\x123\n\x08reconfig\x18\
+    \x08\x20\x01(\x0b2\x17.epaxos.ReconfigurationR\x08reconfig\x12\x18\n\x07\
+    command\x18\t\x20\x01(\x0cR\x07commandJ\x04\x08\x02\x10\x03\"w\n\x12PreA\
+    cceptOKPayload\x12\x0e\n\x02ok\x18\x01\x20\x01(\x08R\x02ok\x12&\n\x06bal\
+    lot\x18\x02\x20\x01(\x0b2\x0e.epaxos.BallotR\x06ballot\x12)\n\x07payload\
+    \x18\x03\x20\x01(\x0b2\x0f.epaxos.PayloadR\x07payload\"\xc6\x01\n\x0fAcc\
+    eptOKPayload\x12\x0e\n\x02ok\x18\x02\x20\x01(\x08R\x02ok\x12&\n\x06ballo\
+    t\x18\x03\x20\x01(\x0b2\x0e.epaxos.BallotR\x06ballot\x12,\n\x08instance\
+    \x18\x04\x20\x01(\x0b2\x10.epaxos.InstanceR\x08instance\x12\x18\n\x07com\
+    mand\x18\x05\x20\x01(\x0cR\x07command\x12-\n\tcommitted\x18\x06\x20\x01(\
+    \x0b2\x0f.epaxos.PayloadR\tcommittedJ\x04\x08\x01\x10\x02\"P\n\x06Ballot\
+    \x12\x14\n\x05epoch\x18\x01\x20\x01(\rR\x05epoch\x12\x16\n\x06number\x18\
+    \x02\x20\x01(\rR\x06number\x12\x18\n\x07replica\x18\x03\x20\x01(\rR\x07r\
+    eplica\"f\n\x0ePreparePayload\x12&\n\x06ballot\x18\x01\x20\x01(\x0b2\x0e\
+    .epaxos.BallotR\x06ballot\x12,\n\x08instance\x18\x02\x20\x01(\x0b2\x10.e\
+    paxos.InstanceR\x08instance\"\xe2\x01\n\x10PrepareOKPayload\x12\x0e\n\
+    \x02ok\x18\x01\x20\x01(\x08R\x02ok\x12&\n\x06ballot\x18\x02\x20\x01(\x0b\
+    2\x0e.epaxos.BallotR\x06ballot\x12\x18\n\x07replica\x18\x03\x20\x01(\rR\
+    \x07replica\x12,\n\x08instance\x18\x04\x20\x01(\x0b2\x10.epaxos.Instance\
+    R\x08instance\x12#\n\x05state\x18\x05\x20\x01(\x0e2\r.epaxos.StateR\x05s\
+    tate\x12)\n\x07payload\x18\x06\x20\x01(\x0b2\x0f.epaxos.PayloadR\x07payl\
+    oad\"\x8d\x01\n\tWalRecord\x12#\n\x05state\x18\x01\x20\x01(\x0e2\r.epaxo\
+    s.StateR\x05state\x12)\n\x07payload\x18\x02\x20\x01(\x0b2\x0f.epaxos.Pay\
+    loadR\x07payload\x120\n\x07promise\x18\x03\x20\x01(\x0b2\x16.epaxos.Prep\
+    arePayloadR\x07promise\"B\n\nWatermarks\x12\x18\n\x07replica\x18\x01\x20\
+    \x01(\rR\x07replica\x12\x1a\n\x08executed\x18\x02\x20\x03(\rR\x08execute\
+    d\"2\n\x08KeyValue\x12\x10\n\x03key\x18\x01\x20\x01(\x0cR\x03key\x12\x14\
+    \n\x05value\x18\x02\x20\x01(\x0cR\x05value\"5\n\x07KvStore\x12*\n\x07ent\
+    ries\x18\x01\x20\x03(\x0b2\x10.epaxos.KeyValueR\x07entries\"\xd1\x01\n\
+    \x08Snapshot\x12\x14\n\x05state\x18\x01\x20\x01(\x0cR\x05state\x12\x1e\n\
+    \nwatermarks\x18\x02\x20\x03(\rR\nwatermarks\x12\x1c\n\ttruncated\x18\
+    \x03\x20\x03(\rR\ttruncated\x12,\n\x08executed\x18\x04\x20\x03(\x0b2\x10\
+    .epaxos.InstanceR\x08executed\x12\x14\n\x05epoch\x18\x05\x20\x01(\rR\x05\
+    epoch\x12-\n\x07members\x18\x06\x20\x03(\x0b2\x13.epaxos.ReplicaInfoR\
+    \x07members\"v\n\x0eCatchUpRequest\x12\x18\n\x07replica\x18\x01\x20\x01(\
+    \rR\x07replica\x12\x1a\n\x08executed\x18\x02\x20\x03(\rR\x08executed\x12\
+    .\n\tcommitted\x18\x03\x20\x03(\x0b2\x10.epaxos.InstanceR\tcommitted\"k\
+    \n\x0cCatchUpReply\x12-\n\tcommitted\x18\x01\x20\x03(\x0b2\x0f.epaxos.Pa\
+    yloadR\tcommitted\x12,\n\x08snapshot\x18\x02\x20\x01(\x0b2\x10.epaxos.Sn\
+    apshotR\x08snapshot\"\x07\n\x05Empty*'\n\x10MembershipChange\x12\x07\n\
+    \x03ADD\x10\0\x12\n\n\x06REMOVE\x10\x01*@\n\x05State\x12\x08\n\x04NONE\
+    \x10\0\x12\x10\n\x0cPRE_ACCEPTED\x10\x01\x12\x0c\n\x08ACCEPTED\x10\x02\
+    \x12\r\n\tCOMMITTED\x10\x032\x8e\x03\n\x0eEpaxosInternal\x129\n\npre_acc\
+    ept\x12\x0f.epaxos.Payload\x1a\x1a.epaxos.PreAcceptOKPayload\x122\n\x06a\
+    ccept\x12\x0f.epaxos.Payload\x1a\x17.epaxos.AcceptOKPayload\x12(\n\x06co\
+    mmit\x12\x0f.epaxos.Payload\x1a\r.epaxos.Empty\x12;\n\x07prepare\x12\x16\
+    .epaxos.PreparePayload\x1a\x18.epaxos.PrepareOKPayload\x12-\n\x08execute\
+    d\x12\x12.epaxos.Watermarks\x1a\r.epaxos.Empty\x128\n\x08catch_up\x12\
+    \x16.epaxos.CatchUpRequest\x1a\x14.epaxos.CatchUpReply\x12=\n\x0breconfi\
+    gure\x12\x17.epaxos.Reconfiguration\x1a\x15.epaxos.WriteResponse2\xaa\
+    \x01\n\rEpaxosService\x124\n\x05write\x12\x14.epaxos.WriteRequest\x1a\
+    \x15.epaxos.WriteResponse\x121\n\x04read\x12\x13.epaxos.ReadRequest\x1a\
+    \x14.epaxos.ReadResponse\x120\n\x06submit\x12\x0f.epaxos.Command\x1a\x15\
+    .epaxos.CommandResultb\x06proto3\
 ";
 
 static mut file_descriptor_proto_lazy: ::protobuf::lazy::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::lazy::Lazy {
@@ -21,59 +21,77 @@
 
 // interface
 
-pub trait EpaxosService {
-    fn pre_accept(&self, o: ::grpc::RequestOptions, p: super::epaxos::Payload) -> ::grpc::SingleResponse<super::epaxos::Payload>;
+pub trait EpaxosInternal {
+    fn pre_accept(&self, o: ::grpc::RequestOptions, p: super::epaxos::Payload) -> ::grpc::SingleResponse<super::epaxos::PreAcceptOKPayload>;
 
     fn accept(&self, o: ::grpc::RequestOptions, p: super::epaxos::Payload) -> ::grpc::SingleResponse<super::epaxos::AcceptOKPayload>;
 
     fn commit(&self, o: ::grpc::RequestOptions, p: super::epaxos::Payload) -> ::grpc::SingleResponse<super::epaxos::Empty>;
 
-    fn write(&self, o: ::grpc::RequestOptions, p: super::epaxos::WriteRequest) -> ::grpc::SingleResponse<super::epaxos::WriteResponse>;
+    fn prepare(&self, o: ::grpc::RequestOptions, p: super::epaxos::PreparePayload) -> ::grpc::SingleResponse<super::epaxos::PrepareOKPayload>;
 
-    fn read(&self, o: ::grpc::RequestOptions, p: super::epaxos::ReadRequest) -> ::grpc::SingleResponse<super::epaxos::ReadResponse>;
+    fn executed(&self, o: ::grpc::RequestOptions, p: super::epaxos::Watermarks) -> ::grpc::SingleResponse<super::epaxos::Empty>;
+
+    fn catch_up(&self, o: ::grpc::RequestOptions, p: super::epaxos::CatchUpRequest) -> ::grpc::SingleResponse<super::epaxos::CatchUpReply>;
+
+    fn reconfigure(&self, o: ::grpc::RequestOptions, p: super::epaxos::Reconfiguration) -> ::grpc::SingleResponse<super::epaxos::WriteResponse>;
 }
 
 // client
 
-pub struct EpaxosServiceClient {
+pub struct EpaxosInternalClient {
     grpc_client: ::std::sync::Arc<::grpc::Client>,
-    method_pre_accept: ::std::sync::Arc<::grpc::rt::MethodDescriptor<super::epaxos::Payload, super::epaxos::Payload>>,
+    method_pre_accept: ::std::sync::Arc<::grpc::rt::MethodDescriptor<super::epaxos::Payload, super::epaxos::PreAcceptOKPayload>>,
     method_accept: ::std::sync::Arc<::grpc::rt::MethodDescriptor<super::epaxos::Payload, super::epaxos::AcceptOKPayload>>,
     method_commit: ::std::sync::Arc<::grpc::rt::MethodDescriptor<super::epaxos::Payload, super::epaxos::Empty>>,
-    method_write: ::std::sync::Arc<::grpc::rt::MethodDescriptor<super::epaxos::WriteRequest, super::epaxos::WriteResponse>>,
-    method_read: ::std::sync::Arc<::grpc::rt::MethodDescriptor<super::epaxos::ReadRequest, super::epaxos::ReadResponse>>,
+    method_prepare: ::std::sync::Arc<::grpc::rt::MethodDescriptor<super::epaxos::PreparePayload, super::epaxos::PrepareOKPayload>>,
+    method_executed: ::std::sync::Arc<::grpc::rt::MethodDescriptor<super::epaxos::Watermarks, super::epaxos::Empty>>,
+    method_catch_up: ::std::sync::Arc<::grpc::rt::MethodDescriptor<super::epaxos::CatchUpRequest, super::epaxos::CatchUpReply>>,
+    method_reconfigure: ::std::sync::Arc<::grpc::rt::MethodDescriptor<super::epaxos::Reconfiguration, super::epaxos::WriteResponse>>,
 }
 
-impl ::grpc::ClientStub for EpaxosServiceClient {
+impl ::grpc::ClientStub for EpaxosInternalClient {
     fn with_client(grpc_client: ::std::sync::Arc<::grpc::Client>) -> Self {
-        EpaxosServiceClient {
+        EpaxosInternalClient {
             grpc_client: grpc_client,
             method_pre_accept: ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
-                name: "/epaxos.EpaxosService/pre_accept".to_string(),
+                name: "/epaxos.EpaxosInternal/pre_accept".to_string(),
                 streaming: ::grpc::rt::GrpcStreaming::Unary,
                 req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
                 resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
             }),
             method_accept: ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
-                name: "/epaxos.EpaxosService/accept".to_string(),
+                name: "/epaxos.EpaxosInternal/accept".to_string(),
                 streaming: ::grpc::rt::GrpcStreaming::Unary,
                 req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
                 resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
             }),
             method_commit: ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
-                name: "/epaxos.EpaxosService/commit".to_string(),
+                name: "/epaxos.EpaxosInternal/commit".to_string(),
                 streaming: ::grpc::rt::GrpcStreaming::Unary,
                 req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
                 resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
             }),
-            method_write: ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
-                name: "/epaxos.EpaxosService/write".to_string(),
+            method_prepare: ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                name: "/epaxos.EpaxosInternal/prepare".to_string(),
                 streaming: ::grpc::rt::GrpcStreaming::Unary,
                 req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
                 resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
             }),
-            method_read: ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
-                name: "/epaxos.EpaxosService/read".to_string(),
+            method_executed: ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                name: "/epaxos.EpaxosInternal/executed".to_string(),
+                streaming: ::grpc::rt::GrpcStreaming::Unary,
+                req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+            }),
+            method_catch_up: ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                name: "/epaxos.EpaxosInternal/catch_up".to_string(),
+                streaming: ::grpc::rt::GrpcStreaming::Unary,
+                req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+            }),
+            method_reconfigure: ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                name: "/epaxos.EpaxosInternal/reconfigure".to_string(),
                 streaming: ::grpc::rt::GrpcStreaming::Unary,
                 req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
                 resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
@@ -82,8 +100,8 @@ impl ::grpc::ClientStub for EpaxosServiceClient {
     }
 }
 
-impl EpaxosService for EpaxosServiceClient {
-    fn pre_accept(&self, o: ::grpc::RequestOptions, p: super::epaxos::Payload) -> ::grpc::SingleResponse<super::epaxos::Payload> {
+impl EpaxosInternal for EpaxosInternalClient {
+    fn pre_accept(&self, o: ::grpc::RequestOptions, p: super::epaxos::Payload) -> ::grpc::SingleResponse<super::epaxos::PreAcceptOKPayload> {
         self.grpc_client.call_unary(o, p, self.method_pre_accept.clone())
     }
 
@@ -95,28 +113,36 @@ impl EpaxosService for EpaxosServiceClient {
         self.grpc_client.call_unary(o, p, self.method_commit.clone())
     }
 
-    fn write(&self, o: ::grpc::RequestOptions, p: super::epaxos::WriteRequest) -> ::grpc::SingleResponse<super::epaxos::WriteResponse> {
-        self.grpc_client.call_unary(o, p, self.method_write.clone())
+    fn prepare(&self, o: ::grpc::RequestOptions, p: super::epaxos::PreparePayload) -> ::grpc::SingleResponse<super::epaxos::PrepareOKPayload> {
+        self.grpc_client.call_unary(o, p, self.method_prepare.clone())
     }
 
-    fn read(&self, o: ::grpc::RequestOptions, p: super::epaxos::ReadRequest) -> ::grpc::SingleResponse<super::epaxos::ReadResponse> {
-        self.grpc_client.call_unary(o, p, self.method_read.clone())
+    fn executed(&self, o: ::grpc::RequestOptions, p: super::epaxos::Watermarks) -> ::grpc::SingleResponse<super::epaxos::Empty> {
+        self.grpc_client.call_unary(o, p, self.method_executed.clone())
+    }
+
+    fn catch_up(&self, o: ::grpc::RequestOptions, p: super::epaxos::CatchUpRequest) -> ::grpc::SingleResponse<super::epaxos::CatchUpReply> {
+        self.grpc_client.call_unary(o, p, self.method_catch_up.clone())
+    }
+
+    fn reconfigure(&self, o: ::grpc::RequestOptions, p: super::epaxos::Reconfiguration) -> ::grpc::SingleResponse<super::epaxos::WriteResponse> {
+        self.grpc_client.call_unary(o, p, self.method_reconfigure.clone())
     }
 }
 
 // server
 
-pub struct EpaxosServiceServer;
+pub struct EpaxosInternalServer;
 
 
-impl EpaxosServiceServer {
-    pub fn new_service_def<H : EpaxosService + 'static + Sync + Send + 'static>(handler: H) -> ::grpc::rt::ServerServiceDefinition {
+impl EpaxosInternalServer {
+    pub fn new_service_def<H : EpaxosInternal + 'static + Sync + Send + 'static>(handler: H) -> ::grpc::rt::ServerServiceDefinition {
         let handler_arc = ::std::sync::Arc::new(handler);
-        ::grpc::rt::ServerServiceDefinition::new("/epaxos.EpaxosService",
+        ::grpc::rt::ServerServiceDefinition::new("/epaxos.EpaxosInternal",
             vec![
                 ::grpc::rt::ServerMethod::new(
                     ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
-                        name: "/epaxos.EpaxosService/pre_accept".to_string(),
+                        name: "/epaxos.EpaxosInternal/pre_accept".to_string(),
                         streaming: ::grpc::rt::GrpcStreaming::Unary,
                         req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
                         resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
@@ -128,7 +154,7 @@ impl EpaxosServiceServer {
                 ),
                 ::grpc::rt::ServerMethod::new(
                     ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
-                        name: "/epaxos.EpaxosService/accept".to_string(),
+                        name: "/epaxos.EpaxosInternal/accept".to_string(),
                         streaming: ::grpc::rt::GrpcStreaming::Unary,
                         req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
                         resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
@@ -140,7 +166,7 @@ impl EpaxosServiceServer {
                 ),
                 ::grpc::rt::ServerMethod::new(
                     ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
-                        name: "/epaxos.EpaxosService/commit".to_string(),
+                        name: "/epaxos.EpaxosInternal/commit".to_string(),
                         streaming: ::grpc::rt::GrpcStreaming::Unary,
                         req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
                         resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
@@ -150,6 +176,128 @@ impl EpaxosServiceServer {
                         ::grpc::rt::MethodHandlerUnary::new(move |o, p| handler_copy.commit(o, p))
                     },
                 ),
+                ::grpc::rt::ServerMethod::new(
+                    ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                        name: "/epaxos.EpaxosInternal/prepare".to_string(),
+                        streaming: ::grpc::rt::GrpcStreaming::Unary,
+                        req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                        resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                    }),
+                    {
+                        let handler_copy = handler_arc.clone();
+                        ::grpc::rt::MethodHandlerUnary::new(move |o, p| handler_copy.prepare(o, p))
+                    },
+                ),
+                ::grpc::rt::ServerMethod::new(
+                    ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                        name: "/epaxos.EpaxosInternal/executed".to_string(),
+                        streaming: ::grpc::rt::GrpcStreaming::Unary,
+                        req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                        resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                    }),
+                    {
+                        let handler_copy = handler_arc.clone();
+                        ::grpc::rt::MethodHandlerUnary::new(move |o, p| handler_copy.executed(o, p))
+                    },
+                ),
+                ::grpc::rt::ServerMethod::new(
+                    ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                        name: "/epaxos.EpaxosInternal/catch_up".to_string(),
+                        streaming: ::grpc::rt::GrpcStreaming::Unary,
+                        req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                        resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                    }),
+                    {
+                        let handler_copy = handler_arc.clone();
+                        ::grpc::rt::MethodHandlerUnary::new(move |o, p| handler_copy.catch_up(o, p))
+                    },
+                ),
+                ::grpc::rt::ServerMethod::new(
+                    ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                        name: "/epaxos.EpaxosInternal/reconfigure".to_string(),
+                        streaming: ::grpc::rt::GrpcStreaming::Unary,
+                        req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                        resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                    }),
+                    {
+                        let handler_copy = handler_arc.clone();
+                        ::grpc::rt::MethodHandlerUnary::new(move |o, p| handler_copy.reconfigure(o, p))
+                    },
+                ),
+            ],
+        )
+    }
+}
+
+// interface
+
+pub trait EpaxosService {
+    fn write(&self, o: ::grpc::RequestOptions, p: super::epaxos::WriteRequest) -> ::grpc::SingleResponse<super::epaxos::WriteResponse>;
+
+    fn read(&self, o: ::grpc::RequestOptions, p: super::epaxos::ReadRequest) -> ::grpc::SingleResponse<super::epaxos::ReadResponse>;
+
+    fn submit(&self, o: ::grpc::RequestOptions, p: super::epaxos::Command) -> ::grpc::SingleResponse<super::epaxos::CommandResult>;
+}
+
+// client
+
+pub struct EpaxosServiceClient {
+    grpc_client: ::std::sync::Arc<::grpc::Client>,
+    method_write: ::std::sync::Arc<::grpc::rt::MethodDescriptor<super::epaxos::WriteRequest, super::epaxos::WriteResponse>>,
+    method_read: ::std::sync::Arc<::grpc::rt::MethodDescriptor<super::epaxos::ReadRequest, super::epaxos::ReadResponse>>,
+    method_submit: ::std::sync::Arc<::grpc::rt::MethodDescriptor<super::epaxos::Command, super::epaxos::CommandResult>>,
+}
+
+impl ::grpc::ClientStub for EpaxosServiceClient {
+    fn with_client(grpc_client: ::std::sync::Arc<::grpc::Client>) -> Self {
+        EpaxosServiceClient {
+            grpc_client: grpc_client,
+            method_write: ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                name: "/epaxos.EpaxosService/write".to_string(),
+                streaming: ::grpc::rt::GrpcStreaming::Unary,
+                req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+            }),
+            method_read: ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                name: "/epaxos.EpaxosService/read".to_string(),
+                streaming: ::grpc::rt::GrpcStreaming::Unary,
+                req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+            }),
+            method_submit: ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                name: "/epaxos.EpaxosService/submit".to_string(),
+                streaming: ::grpc::rt::GrpcStreaming::Unary,
+                req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+            }),
+        }
+    }
+}
+
+impl EpaxosService for EpaxosServiceClient {
+    fn write(&self, o: ::grpc::RequestOptions, p: super::epaxos::WriteRequest) -> ::grpc::SingleResponse<super::epaxos::WriteResponse> {
+        self.grpc_client.call_unary(o, p, self.method_write.clone())
+    }
+
+    fn read(&self, o: ::grpc::RequestOptions, p: super::epaxos::ReadRequest) -> ::grpc::SingleResponse<super::epaxos::ReadResponse> {
+        self.grpc_client.call_unary(o, p, self.method_read.clone())
+    }
+
+    fn submit(&self, o: ::grpc::RequestOptions, p: super::epaxos::Command) -> ::grpc::SingleResponse<super::epaxos::CommandResult> {
+        self.grpc_client.call_unary(o, p, self.method_submit.clone())
+    }
+}
+
+// server
+
+pub struct EpaxosServiceServer;
+
+
+impl EpaxosServiceServer {
+    pub fn new_service_def<H : EpaxosService + 'static + Sync + Send + 'static>(handler: H) -> ::grpc::rt::ServerServiceDefinition {
+        let handler_arc = ::std::sync::Arc::new(handler);
+        ::grpc::rt::ServerServiceDefinition::new("/epaxos.EpaxosService",
+            vec![
                 ::grpc::rt::ServerMethod::new(
                     ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
                         name: "/epaxos.EpaxosService/write".to_string(),
@@ -174,6 +322,18 @@ impl EpaxosServiceServer {
                         ::grpc::rt::MethodHandlerUnary::new(move |o, p| handler_copy.read(o, p))
                     },
                 ),
+                ::grpc::rt::ServerMethod::new(
+                    ::std::sync::Arc::new(::grpc::rt::MethodDescriptor {
+                        name: "/epaxos.EpaxosService/submit".to_string(),
+                        streaming: ::grpc::rt::GrpcStreaming::Unary,
+                        req_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                        resp_marshaller: Box::new(::grpc::protobuf::MarshallerProtobuf),
+                    }),
+                    {
+                        let handler_copy = handler_arc.clone();
+                        ::grpc::rt::MethodHandlerUnary::new(move |o, p| handler_copy.submit(o, p))
+                    },
+                ),
             ],
         )
     }
@@ -0,0 +1,290 @@
+use crate::command::Replicated;
+use crate::logic::*;
+use std::{
+    cmp,
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+};
+
+// Tarjan's strongly connected components over the dependency graph.
+// Components come out in reverse topological order, so by the time a
+// component is popped, everything it depends on has already been decided.
+struct Tarjan<'a, C> {
+    logic: &'a EpaxosLogic<C>,
+    index: u32,
+    indices: HashMap<Instance, u32>,
+    lowlinks: HashMap<Instance, u32>,
+    stack: Vec<Instance>,
+    on_stack: HashSet<Instance>,
+    // Instances that (transitively) depend on something not committed yet
+    blocked: HashSet<Instance>,
+    order: Vec<Instance>,
+}
+
+impl<'a, C: Replicated> Tarjan<'a, C> {
+    fn new(logic: &'a EpaxosLogic<C>) -> Self {
+        Tarjan {
+            logic,
+            index: 0,
+            indices: HashMap::new(),
+            lowlinks: HashMap::new(),
+            stack: Vec::new(),
+            on_stack: HashSet::new(),
+            blocked: HashSet::new(),
+            order: Vec::new(),
+        }
+    }
+
+    fn visit(&mut self, v: Instance) {
+        self.indices.insert(v, self.index);
+        self.lowlinks.insert(v, self.index);
+        self.index += 1;
+        self.stack.push(v);
+        self.on_stack.insert(v);
+    }
+
+    // Depth-first search from `root`. It keeps its own stack of the
+    // instances it is in the middle of, and how many of their deps it has
+    // gone through, as a chain of dependencies can be as long as the log.
+    fn strong_connect(&mut self, root: Instance) {
+        let logic = self.logic;
+        let mut calls: Vec<(Instance, usize)> = vec![(root, 0)];
+        self.visit(root);
+        while let Some((v, next)) = calls.last_mut() {
+            let v = *v;
+            if let Some(w) = logic.get(&v).unwrap().deps.get(*next).cloned() {
+                *next += 1;
+                match logic.state_of(&w) {
+                    Some(State::Executed) => continue,
+                    Some(State::Committed) => {}
+                    // Missing, PreAccepted or Accepted: we have to wait
+                    _ => {
+                        self.blocked.insert(v);
+                        continue;
+                    }
+                }
+                if !self.indices.contains_key(&w) {
+                    self.visit(w);
+                    calls.push((w, 0));
+                    continue;
+                }
+                if self.on_stack.contains(&w) {
+                    let low = cmp::min(self.lowlinks[&v], self.indices[&w]);
+                    self.lowlinks.insert(v, low);
+                }
+                if self.blocked.contains(&w) {
+                    self.blocked.insert(v);
+                }
+                continue;
+            }
+            // Done with v, back to whoever depends on it
+            calls.pop();
+            self.pop_component(v);
+            if let Some((u, _)) = calls.last() {
+                let u = *u;
+                let low = cmp::min(self.lowlinks[&u], self.lowlinks[&v]);
+                self.lowlinks.insert(u, low);
+                if self.blocked.contains(&v) {
+                    self.blocked.insert(u);
+                }
+            }
+        }
+    }
+
+    // Once v and all its deps are done, takes the component v is the root
+    // of, if any, off the stack
+    fn pop_component(&mut self, v: Instance) {
+        if self.lowlinks[&v] == self.indices[&v] {
+            let mut component = Vec::new();
+            loop {
+                let w = self.stack.pop().unwrap();
+                self.on_stack.remove(&w);
+                component.push(w);
+                if w == v {
+                    break;
+                }
+            }
+            if component.iter().any(|w| self.blocked.contains(w)) {
+                for w in component {
+                    self.blocked.insert(w);
+                }
+                return;
+            }
+            // Inside a component, execute in increasing seq order
+            let logic = self.logic;
+            component.sort_by(|a, b| {
+                match logic.get(a).unwrap().seq.cmp(&logic.get(b).unwrap().seq) {
+                    Ordering::Equal => sort_instances(a, b),
+                    ordering => ordering,
+                }
+            });
+            self.order.append(&mut component);
+        }
+    }
+}
+
+impl<C: Replicated> EpaxosLogic<C> {
+    /// Runs the EPaxos execution algorithm over every committed instance
+    /// whose dependencies are all committed, marks them as executed and
+    /// returns them in the order they must be applied to the store.
+    pub fn execute(&mut self) -> Vec<(Instance, LogEntry<C>)> {
+        let mut committed: Vec<Instance> = self.unexecuted.iter().cloned().collect();
+        committed.sort_by(sort_instances);
+
+        let order = {
+            let mut tarjan = Tarjan::new(self);
+            for instance in committed {
+                if !tarjan.indices.contains_key(&instance) {
+                    tarjan.strong_connect(instance);
+                }
+            }
+            tarjan.order
+        };
+
+        let mut executed = Vec::new();
+        for instance in order {
+            let log_entry = self.cmds[instance.replica as usize]
+                .get_mut(&(instance.slot as usize))
+                .unwrap();
+            log_entry.state = State::Executed;
+            let log_entry = log_entry.clone();
+            self.unexecuted.remove(&instance);
+            if let Some(reconfig) = log_entry.reconfig.as_ref() {
+                self.reconfigure(reconfig);
+            }
+            executed.push((instance, log_entry));
+        }
+        self.advance_watermarks();
+        executed
+    }
+
+    /// Instances that committed commands are waiting on but that have not
+    /// been committed here. If their leader failed, they need recovery.
+    pub fn blocking_instances(&self) -> Vec<Instance> {
+        let mut blocking = Vec::new();
+        for instance in self.unexecuted.iter() {
+            for dep in self.get(instance).unwrap().deps.iter() {
+                match self.state_of(dep) {
+                    Some(State::Committed) | Some(State::Executed) => {}
+                    _ => blocking.push(*dep),
+                }
+            }
+        }
+        blocking.sort_by(sort_instances);
+        blocking.dedup();
+        blocking
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::Command;
+    use crate::topology::Topology;
+
+    fn logic() -> EpaxosLogic {
+        EpaxosLogic::init(ReplicaId(0), &Topology::ec2())
+    }
+
+    fn put(
+        logic: &mut EpaxosLogic,
+        instance: Instance,
+        seq: u32,
+        deps: Vec<Instance>,
+        state: State,
+    ) {
+        let log_entry = LogEntry {
+            command: Command::Put {
+                key: b"x".to_vec(),
+                value: b"1".to_vec(),
+            },
+            seq,
+            deps,
+            state,
+            noop: false,
+            ballot: Ballot::default(),
+            epoch: 0,
+            reconfig: None,
+        };
+        logic.update_log(log_entry, &instance);
+    }
+
+    fn commit(logic: &mut EpaxosLogic, instance: Instance, seq: u32, deps: Vec<Instance>) {
+        put(logic, instance, seq, deps, State::Committed);
+    }
+
+    fn executed(logic: &mut EpaxosLogic) -> Vec<Instance> {
+        logic
+            .execute()
+            .into_iter()
+            .map(|(instance, _)| instance)
+            .collect()
+    }
+
+    #[test]
+    fn chain_executes_dependencies_first() {
+        let mut logic = logic();
+        let (a, b, c) = (Instance::at(0, 0), Instance::at(1, 0), Instance::at(2, 0));
+        commit(&mut logic, c, 3, vec![b]);
+        commit(&mut logic, a, 1, vec![]);
+        commit(&mut logic, b, 2, vec![a]);
+        assert_eq!(executed(&mut logic), vec![a, b, c]);
+        assert!(executed(&mut logic).is_empty());
+        assert!(logic.unexecuted.is_empty());
+    }
+
+    #[test]
+    fn long_chain_does_not_overflow_the_stack() {
+        let mut logic = logic();
+        let length = 100_000;
+        // Each slot depends on the next one, so the search from slot 0 goes
+        // all the way down before anything executes
+        for slot in 0..length {
+            let deps = if slot + 1 < length {
+                vec![Instance::at(0, slot + 1)]
+            } else {
+                vec![]
+            };
+            commit(&mut logic, Instance::at(0, slot), length - slot, deps);
+        }
+        let order = executed(&mut logic);
+        let expected: Vec<Instance> = (0..length)
+            .rev()
+            .map(|slot| Instance::at(0, slot))
+            .collect();
+        assert!(order == expected);
+    }
+
+    #[test]
+    fn cycle_executes_in_seq_order_then_by_instance() {
+        let mut logic = logic();
+        let (a, b) = (Instance::at(0, 0), Instance::at(1, 0));
+        commit(&mut logic, a, 2, vec![b]);
+        commit(&mut logic, b, 1, vec![a]);
+        // Same seq on both sides
+        let (c, d) = (Instance::at(2, 0), Instance::at(3, 0));
+        commit(&mut logic, d, 3, vec![c, a]);
+        commit(&mut logic, c, 3, vec![d]);
+        assert_eq!(executed(&mut logic), vec![b, a, c, d]);
+    }
+
+    #[test]
+    fn blocked_dependency_holds_back_its_dependents_only() {
+        let mut logic = logic();
+        let (a, x, b, c) = (
+            Instance::at(0, 0),
+            Instance::at(1, 0),
+            Instance::at(2, 0),
+            Instance::at(3, 0),
+        );
+        commit(&mut logic, a, 2, vec![x]);
+        put(&mut logic, x, 1, vec![], State::PreAccepted);
+        commit(&mut logic, b, 3, vec![a]);
+        commit(&mut logic, c, 1, vec![]);
+        assert_eq!(executed(&mut logic), vec![c]);
+        assert_eq!(logic.blocking_instances(), vec![x]);
+        commit(&mut logic, x, 1, vec![]);
+        assert_eq!(executed(&mut logic), vec![x, a, b]);
+        assert!(logic.blocking_instances().is_empty());
+    }
+}
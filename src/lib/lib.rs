@@ -1,9 +1,26 @@
+extern crate crc32fast;
+extern crate crossbeam;
 extern crate futures;
+extern crate futures01;
 extern crate futures_cpupool;
 extern crate grpc;
 extern crate protobuf;
+extern crate serde;
+extern crate tokio_timer;
+extern crate toml;
 
+pub mod catch_up;
+pub mod command;
 pub mod converter;
+// Generated by build.rs from epaxos.proto
+#[allow(warnings)]
 pub mod epaxos;
+#[allow(warnings)]
 pub mod epaxos_grpc;
+pub mod execution;
 pub mod logic;
+pub mod server;
+pub mod snapshot;
+pub mod state_machine;
+pub mod topology;
+pub mod wal;
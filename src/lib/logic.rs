@@ -1,10 +1,18 @@
 extern crate protobuf;
 
-use std::{cmp, cmp::Ordering, collections::HashMap, fmt, time::Duration};
+use crate::command::{Command, Key, Replicated, Value};
+use crate::topology::{ReplicaConfig, Topology};
+use crate::wal::{Record, Wal};
+use std::{
+    cmp,
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    fmt,
+    hash::{Hash, Hasher},
+    io, path,
+    time::Duration,
+};
 
-pub const SLOW_QUORUM: usize = 3; // F + 1
-pub const FAST_QUORUM: usize = 3; // F + floor(F + 1 / 2)
-pub const REPLICAS_NUM: usize = 5;
 pub const LOCALHOST: &str = "127.0.0.1";
 pub const VA: &str = "52.23.98.238";
 pub const NORCA: &str = "52.53.140.242";
@@ -12,15 +20,59 @@ pub const OR: &str = "54.68.85.53";
 pub const JP: &str = "18.176.188.121";
 pub const EU: &str = "108.128.186.5";
 pub const REPLICA_PORT: u16 = 10000;
-pub static REPLICA_ADDRESSES: [&str; REPLICAS_NUM] = [VA, JP, NORCA, OR, EU];
+// The replica set we deploy to unless told otherwise, indexed by ReplicaId
+pub static REPLICA_ADDRESSES: &[&str] = &[VA, JP, NORCA, OR, EU];
+// How long an instance may block execution before we try to recover it
+pub const RECOVERY_TIMEOUT: Duration = Duration::from_secs(2);
+// Default deadline for the replies of one round of replica-to-replica RPCs
+pub const RPC_TIMEOUT: Duration = Duration::from_secs(1);
+// How often we snapshot the store and drop what every replica has executed
+pub const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(10);
+// How long a Commit may go unacknowledged before we send it again
+pub const COMMIT_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+// How many unacknowledged Commits a peer gets again in one round
+pub const COMMIT_RETRY_BATCH: usize = 100;
+// Rounds in a row a peer may leave unanswered before we count it as down,
+// stop keeping its Commits and leave it to catch up. Each round waits twice
+// as long as the one before.
+pub const COMMIT_RETRY_LIMIT: u32 = 5;
+// How often we check whether we missed instances and ask a peer for them
+pub const CATCH_UP_INTERVAL: Duration = Duration::from_secs(1);
+// Default for how long a log record waits for others to share its fsync
+pub const GROUP_COMMIT_WAIT: Duration = Duration::from_micros(500);
+// How many instances of one replica that do not interfere with each other,
+// such as reads, a key gathers before the replica's next command on the key
+// depends on all of them. Once committed, it stands in for them, so that
+// later commands need not depend on each of them.
+pub const MAX_KEY_CONFLICTS: usize = 64;
+// Default for the largest encoded command a request may carry, in bytes
+pub const MAX_COMMAND_SIZE: usize = 1 << 20;
 
 #[derive(PartialEq, Eq, Hash, Clone, Debug, Copy)]
 pub struct ReplicaId(pub u32);
 
-#[derive(Debug, Clone)]
+// F, the number of failures a cluster of this many replicas tolerates
+pub const fn max_failures(replicas: usize) -> usize {
+    (replicas - 1) / 2
+}
+
+// F + 1, leader included
+pub const fn slow_quorum_size(replicas: usize) -> usize {
+    max_failures(replicas) + 1
+}
+
+// 2F, leader included. Any F + 1 replicas other than the leader then hold
+// at least F of a fast quorum, which is what `decide_recovery` relies on.
+// With more than N - 2F replicas down, no fast quorum answers, and the
+// leader goes on with a slow quorum, see `end_pre_accept`.
+pub const fn fast_quorum_size(replicas: usize) -> usize {
+    2 * max_failures(replicas)
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct WriteRequest {
-    pub key: String,
-    pub value: i32,
+    pub key: Key,
+    pub value: Value,
 }
 
 #[derive(Clone, Copy)]
@@ -30,63 +82,258 @@ pub struct WriteResponse {
 
 #[derive(Clone)]
 pub struct ReadRequest {
-    pub key: String,
+    pub key: Key,
+    // Order the read with the writes through consensus instead of
+    // answering from our store, which may not have seen the latest commits
+    pub linearizable: bool,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub struct ReadResponse {
-    pub value: i32,
+    // None if the key is unset
+    pub value: Option<Value>,
 }
 
-#[derive(Clone)]
+// Why a request could not be served
+#[derive(Debug)]
+pub enum EpaxosError {
+    // Not enough replies arrived before the deadline
+    Timeout(&'static str),
+    // All replies arrived, but too many calls failed to make a quorum
+    NoQuorum(&'static str),
+    // Another replica took the instance over with a higher ballot
+    Preempted,
+    // We are not in the replica set, or not anymore
+    NotMember,
+    // A request carried a command larger than we accept
+    CommandTooLarge { size: usize, max: usize },
+}
+
+impl fmt::Display for EpaxosError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EpaxosError::Timeout(stage) => {
+                write!(f, "[{}] timed out", stage)
+            }
+            EpaxosError::NoQuorum(stage) => write!(f, "[{}] too few replicas replied", stage),
+            EpaxosError::Preempted => write!(f, "preempted by a higher ballot"),
+            EpaxosError::NotMember => write!(f, "not a member of the replica set"),
+            EpaxosError::CommandTooLarge { size, max } => write!(
+                f,
+                "command of {} bytes exceeds the maximum of {} bytes",
+                size, max
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EpaxosError {}
+
+// Declared in the order an instance moves through them
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
 pub enum State {
     PreAccepted,
     Accepted,
     Committed,
+    Executed,
 }
 
 #[derive(Debug, Clone)]
-pub struct Payload {
-    pub write_req: WriteRequest,
+pub struct Payload<C = Command> {
+    pub command: C,
     pub seq: u32,
     pub deps: Vec<Instance>,
     pub instance: Instance,
+    pub noop: bool,
+    pub ballot: Ballot,
+    // If set, the instance changes the replica set and `command` is unused
+    pub reconfig: Option<Reconfiguration>,
+}
+
+// A change to the replica set. It is committed like any other command and
+// interferes with all of them, so every replica applies it at the same
+// point in the execution order. See `EpaxosLogic::reconfigure`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reconfiguration {
+    Add(ReplicaConfig),
+    Remove(ReplicaId),
+}
+
+#[derive(Clone)]
+pub struct PreAcceptOKPayload<C = Command> {
+    // false means NACK: `ballot` is then the higher ballot we are bound to
+    pub ok: bool,
+    pub ballot: Ballot,
+    pub payload: Payload<C>,
 }
 
 #[derive(Clone)]
-pub struct AcceptOKPayload {
-    pub write_req: WriteRequest,
+pub struct AcceptOKPayload<C = Command> {
+    pub ok: bool,
+    pub ballot: Ballot,
+    pub command: C,
     pub instance: Instance,
+    // If the instance committed at the replier already, what it committed
+    // with, so that the sender can finish it too
+    pub committed: Option<Payload<C>>,
 }
 
 #[derive(Clone)]
-pub struct LogEntry {
-    pub key: String,
-    pub value: i32,
+pub struct LogEntry<C = Command> {
+    pub command: C,
     pub seq: u32,
     pub deps: Vec<Instance>,
     pub state: State,
+    pub noop: bool,
+    // Ballot of the last message that updated this entry
+    pub ballot: Ballot,
+    // The epoch the instance was started in
+    pub epoch: u32,
+    pub reconfig: Option<Reconfiguration>,
 }
 
-#[derive(Clone, PartialEq, Copy)]
+// An instance of some replica that a new command may have to depend on,
+// see `conflicts`
+#[derive(Clone, Copy)]
+pub struct Conflict {
+    pub slot: u32,
+    pub seq: u32,
+}
+
+// For one replica and key, the latest barrier, see `Interference`, and the
+// instances after it. A command on the key depends on the barrier, and on
+// those of the others it interferes with.
+#[derive(Clone, Default)]
+pub struct KeyConflicts {
+    pub barrier: Option<Conflict>,
+    pub after: Vec<Conflict>,
+}
+
+// Slots are never reused, not even in a later epoch, so the replica and the
+// slot alone identify an instance. The epoch it was started in comes along
+// but is not compared.
+#[derive(Clone, Copy)]
 pub struct Instance {
     pub replica: u32,
     pub slot: u32,
+    pub epoch: u32,
+}
+
+impl Instance {
+    // For looking an instance up, when its epoch does not matter
+    pub fn at(replica: u32, slot: u32) -> Self {
+        Instance {
+            replica,
+            slot,
+            epoch: 0,
+        }
+    }
+}
+
+impl PartialEq for Instance {
+    fn eq(&self, other: &Instance) -> bool {
+        self.replica == other.replica && self.slot == other.slot
+    }
+}
+
+impl Eq for Instance {}
+
+impl Hash for Instance {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.replica.hash(state);
+        self.slot.hash(state);
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Debug)]
+pub struct Ballot {
+    pub epoch: u32,
+    pub number: u32,
+    pub replica: u32,
+}
+
+#[derive(Clone)]
+pub struct PreparePayload {
+    pub ballot: Ballot,
+    pub instance: Instance,
+}
+
+#[derive(Clone)]
+pub struct PrepareOKPayload<C = Command> {
+    // false means NACK: `ballot` is then the higher ballot we are bound to
+    pub ok: bool,
+    pub ballot: Ballot,
+    pub replica: ReplicaId,
+    pub instance: Instance,
+    pub entry: Option<(State, Payload<C>)>,
+}
+
+pub struct PreAccept<C = Command>(pub Payload<C>);
+
+pub struct Accept<C = Command>(pub Payload<C>);
+
+pub struct Commit<C = Command>(pub Payload<C>);
+
+pub struct PreAcceptOK<C = Command>(pub PreAcceptOKPayload<C>);
+
+pub struct AcceptOK<C = Command>(pub AcceptOKPayload<C>);
+
+pub struct Prepare(pub PreparePayload);
+
+pub struct PrepareOK<C = Command>(pub PrepareOKPayload<C>);
+
+pub enum Path<C = Command> {
+    Slow(Payload<C>),
+    Fast(Payload<C>),
 }
 
-pub struct PreAccept(pub Payload);
+// Phase of an instance we are leading
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Phase {
+    PreAccept,
+    Accept,
+}
 
-pub struct Accept(pub Payload);
+// Replies collected so far for an instance we are leading
+pub struct Leading<C = Command> {
+    pub payload: Payload<C>,
+    pub phase: Phase,
+    pub pre_accept_oks: Vec<Payload<C>>,
+    pub accept_oks: usize,
+}
 
-pub struct Commit(pub Payload);
+// What the command leader has to do next for an instance
+pub enum Step<C = Command> {
+    // Not enough replies yet
+    Wait,
+    // Run Paxos-Accept with these attributes
+    Accept(Payload<C>),
+    // Tell the other replicas that the instance committed
+    Commit(Payload<C>),
+    // Another replica took the instance over with a higher ballot
+    Abort,
+}
 
-pub struct PreAcceptOK(pub Payload);
+// What a replica taking over an instance has to do to finish it
+pub enum Recovery<C = Command> {
+    Commit(Payload<C>),
+    Accept(Payload<C>),
+    PreAccept(Payload<C>),
+}
 
-pub struct AcceptOK(pub AcceptOKPayload);
+// What a command interferes with
+enum Footprint<'a, C> {
+    // The commands it interferes with, see `Interference`
+    Command(&'a C),
+    // Every command, for a reconfiguration
+    Everything,
+}
 
-pub enum Path {
-    Slow(Payload),
-    Fast(Payload),
+fn footprint<'a, C>(command: &'a C, reconfig: &Option<Reconfiguration>) -> Footprint<'a, C> {
+    match reconfig {
+        Some(_) => Footprint::Everything,
+        None => Footprint::Command(command),
+    }
 }
 
 pub fn sort_instances(inst1: &Instance, inst2: &Instance) -> Ordering {
@@ -103,129 +350,555 @@ pub fn sort_instances(inst1: &Instance, inst2: &Instance) -> Ordering {
     }
 }
 
-pub struct EpaxosLogic {
+impl<C: Replicated> Payload<C> {
+    pub fn noop(instance: Instance) -> Self {
+        Payload {
+            command: C::default(),
+            seq: 0,
+            deps: Vec::new(),
+            instance,
+            noop: true,
+            ballot: Ballot::default(),
+            reconfig: None,
+        }
+    }
+
+    pub fn from_log_entry(log_entry: &LogEntry<C>, instance: Instance) -> Self {
+        Payload {
+            command: log_entry.command.clone(),
+            seq: log_entry.seq,
+            deps: log_entry.deps.clone(),
+            instance: Instance {
+                epoch: log_entry.epoch,
+                ..instance
+            },
+            noop: log_entry.noop,
+            ballot: log_entry.ballot,
+            reconfig: log_entry.reconfig.clone(),
+        }
+    }
+}
+
+impl<C: Replicated> LogEntry<C> {
+    pub fn from_payload(payload: &Payload<C>, state: State) -> Self {
+        LogEntry {
+            command: payload.command.clone(),
+            seq: payload.seq,
+            deps: payload.deps.clone(),
+            state,
+            noop: payload.noop,
+            ballot: payload.ballot,
+            epoch: payload.instance.epoch,
+            reconfig: payload.reconfig.clone(),
+        }
+    }
+}
+
+pub struct EpaxosLogic<C = Command> {
     pub id: ReplicaId,
-    pub cmds: Vec<HashMap<usize, LogEntry>>,
+    // Bumped by every reconfiguration we execute. Messages with ballots
+    // from an older epoch are refused.
+    pub epoch: u32,
+    // The replica set of this epoch
+    pub members: Topology,
+    // N, the number of members, and the quorums it implies
+    pub replicas_num: usize,
+    pub slow_quorum: usize,
+    pub fast_quorum: usize,
+    // One row per replica id that ever was a member, or is joining
+    pub cmds: Vec<HashMap<usize, LogEntry<C>>>,
+    // Next free slot in our own row. It only ever grows, and is taken when
+    // an instance starts, so no two instances we lead share a slot.
     pub instance_number: u32,
+    // Highest ballot we promised in a Prepare, per instance
+    pub ballots: HashMap<Instance, Ballot>,
+    // Per key and replica, the latest instances that touched it. A new
+    // command only needs to depend on these, since each of them depends on
+    // the earlier ones of its replica.
+    pub conflicts: HashMap<Key, Vec<KeyConflicts>>,
+    // Per replica, the latest reconfiguration, which every command depends on
+    pub reconfigs: Vec<Option<Conflict>>,
+    // Instances that are committed but not executed yet, so that execution
+    // does not have to look through the whole log
+    pub unexecuted: HashSet<Instance>,
+    // Instances we are the command leader of and that are not committed yet
+    pub leading: HashMap<Instance, Leading<C>>,
+    // Per replica, every slot below watermarks[r] of replica r is executed
+    pub watermarks: Vec<u32>,
+    // The same, as last reported by each of the other replicas
+    pub peer_watermarks: HashMap<ReplicaId, Vec<u32>>,
+    // Per replica, slots below truncated[r] were executed everywhere and are
+    // gone from cmds, see snapshot.rs
+    pub truncated: Vec<u32>,
+    // Where changes to the log and promises are made durable. None when we
+    // keep everything in memory only.
+    wal: Option<Wal>,
 }
 
-impl EpaxosLogic {
-    pub fn init(id: ReplicaId) -> EpaxosLogic {
-        let commands = vec![HashMap::new(); REPLICAS_NUM];
-        EpaxosLogic {
-            id: id,
-            cmds: commands,
+impl<C: Replicated> EpaxosLogic<C> {
+    /// Starts out in epoch 0 with the members of `topology`
+    pub fn init(id: ReplicaId, topology: &Topology) -> EpaxosLogic<C> {
+        let mut logic = EpaxosLogic {
+            id,
+            epoch: 0,
+            members: Topology {
+                replicas: Vec::new(),
+            },
+            replicas_num: 0,
+            slow_quorum: 0,
+            fast_quorum: 0,
+            cmds: Vec::new(),
             instance_number: 0,
+            ballots: HashMap::new(),
+            conflicts: HashMap::new(),
+            reconfigs: Vec::new(),
+            unexecuted: HashSet::new(),
+            leading: HashMap::new(),
+            watermarks: Vec::new(),
+            peer_watermarks: HashMap::new(),
+            truncated: Vec::new(),
+            wal: None,
+        };
+        logic.ensure_rows(topology.rows());
+        logic.set_members(topology.members());
+        logic
+    }
+
+    pub fn set_members(&mut self, members: Topology) {
+        self.ensure_rows(members.rows());
+        self.replicas_num = members.len();
+        self.slow_quorum = slow_quorum_size(members.len());
+        self.fast_quorum = fast_quorum_size(members.len());
+        self.peer_watermarks
+            .retain(|replica, _| members.contains(*replica));
+        self.members = members;
+    }
+
+    pub fn is_member(&self) -> bool {
+        self.members.contains(self.id)
+    }
+
+    // Makes room in the log for replica ids below `rows`
+    pub fn ensure_rows(&mut self, rows: usize) {
+        if self.cmds.len() < rows {
+            self.cmds.resize(rows, HashMap::new());
+            self.watermarks.resize(rows, 0);
+            self.truncated.resize(rows, 0);
         }
     }
 
-    pub fn update_log(&mut self, log_entry: LogEntry, instance: &Instance) {
+    /// Applies a reconfiguration once it is executed, which moves us to the
+    /// next epoch. Every replica executes it at the same point.
+    pub fn reconfigure(&mut self, reconfig: &Reconfiguration) {
+        let mut members = self.members.clone();
+        match reconfig {
+            Reconfiguration::Add(replica) => {
+                members.replicas.retain(|member| member.id != replica.id);
+                members.replicas.push(ReplicaConfig {
+                    joining: false,
+                    ..replica.clone()
+                });
+                members.replicas.sort_by_key(|member| member.id);
+            }
+            Reconfiguration::Remove(id) => members.replicas.retain(|member| member.id != id.0),
+        }
+        if members.is_empty() {
+            println!("Ignoring {:?}, it would leave no members", reconfig);
+            return;
+        }
+        self.epoch += 1;
+        self.set_members(members);
+        println!("Epoch {}: members are {:?}", self.epoch, self.members.ids());
+    }
+
+    /// Like `init`, but backed by the write-ahead log at `wal_path`.
+    /// Whatever the log already holds is replayed first, so a restarted
+    /// replica comes back with the instances and promises it had.
+    pub fn open<P: AsRef<path::Path>>(
+        id: ReplicaId,
+        topology: &Topology,
+        wal_path: P,
+        group_commit_wait: Duration,
+    ) -> io::Result<EpaxosLogic<C>> {
+        let mut logic = EpaxosLogic::init(id, topology);
+        let (wal, records) = Wal::open(wal_path, group_commit_wait)?;
+        println!("Replaying {} records of the write-ahead log", records.len());
+        for record in records {
+            match record {
+                Record::Entry(instance, log_entry) => logic.update_log(log_entry, &instance),
+                Record::Promise(PreparePayload { ballot, instance }) => {
+                    logic.ballots.insert(instance, ballot);
+                }
+            }
+        }
+        logic.wal = Some(wal);
+        Ok(logic)
+    }
+
+    // The log our changes go to. Whoever makes a change has to `sync` it
+    // before telling anyone about it.
+    pub fn wal(&self) -> Option<Wal> {
+        self.wal.clone()
+    }
+
+    fn persist(&mut self, record: Record<C>) {
+        if let Some(wal) = self.wal.as_ref() {
+            wal.append(&record);
+        }
+    }
+
+    pub fn update_log(&mut self, log_entry: LogEntry<C>, instance: &Instance) {
         println!("updating log..");
+        if self.is_truncated(instance) {
+            return;
+        }
+        self.ensure_rows(instance.replica as usize + 1);
+        if let Some(old) = self.get(instance) {
+            // Once committed, an instance never changes again. Before that,
+            // it can only move forward within a ballot, or to a higher ballot.
+            let stale = old.state >= State::Committed
+                || (log_entry.state < State::Committed
+                    && (log_entry.ballot < old.ballot
+                        || (log_entry.ballot == old.ballot && log_entry.state < old.state)));
+            if stale {
+                println!(
+                    "Ignoring stale update of {:?} to {:?}",
+                    instance, log_entry.state
+                );
+                return;
+            }
+        }
+        // Whatever puts an entry in our own row, e.g. replaying our log after
+        // a restart or a peer recovering one of our instances, moves the slot
+        // counter past it
+        if instance.replica == self.id.0 {
+            self.instance_number = cmp::max(self.instance_number, instance.slot + 1);
+        }
+        self.persist(Record::Entry(*instance, log_entry.clone()));
+        if !log_entry.noop {
+            self.index_conflict(&log_entry, instance);
+        }
+        if log_entry.state == State::Committed {
+            self.unexecuted.insert(*instance);
+        }
         self.cmds[instance.replica as usize].insert(instance.slot as usize, log_entry);
     }
 
-    pub fn lead_consensus(&mut self, write_req: WriteRequest) -> Payload {
-        let slot = self.instance_number;
-        let interf = self.find_interference(&write_req.key);
-        let seq = 1 + self.find_max_seq(&interf);
-        let log_entry = LogEntry {
-            key: write_req.key.to_owned(),
-            value: write_req.value,
-            seq: seq,
-            deps: interf.clone(),
-            state: State::PreAccepted,
+    pub fn get(&self, instance: &Instance) -> Option<&LogEntry<C>> {
+        self.cmds
+            .get(instance.replica as usize)
+            .and_then(|log| log.get(&(instance.slot as usize)))
+    }
+
+    pub fn state_of(&self, instance: &Instance) -> Option<State> {
+        if self.is_truncated(instance) {
+            return Some(State::Executed);
+        }
+        self.get(instance).map(|log_entry| log_entry.state)
+    }
+    // Whether every replica executed the instance and we dropped it
+    fn is_truncated(&self, instance: &Instance) -> bool {
+        self.truncated
+            .get(instance.replica as usize)
+            .is_some_and(|truncated| instance.slot < *truncated)
+    }
+
+    // Highest ballot we are bound to for the instance
+    pub fn promised(&self, instance: &Instance) -> Ballot {
+        let promised = self.ballots.get(instance).cloned().unwrap_or_default();
+        match self.get(instance) {
+            Some(log_entry) => cmp::max(promised, log_entry.ballot),
+            None => promised,
+        }
+    }
+
+    // Whether a message at `ballot` that moves the instance to `state` must be refused
+    fn is_stale(&self, instance: &Instance, ballot: Ballot, state: State) -> bool {
+        if ballot.epoch < self.epoch || ballot < self.promised(instance) {
+            return true;
+        }
+        match self.get(instance) {
+            Some(log_entry) => {
+                log_entry.state >= State::Committed
+                    || (ballot == log_entry.ballot && state < log_entry.state)
+            }
+            None => false,
+        }
+    }
+
+    pub fn lead_consensus(
+        &mut self,
+        command: C,
+        reconfig: Option<Reconfiguration>,
+    ) -> Result<Payload<C>, EpaxosError> {
+        if !self.is_member() {
+            return Err(EpaxosError::NotMember);
+        }
+        // The caller holds the lock on us, so taking the slot is atomic
+        let instance = Instance {
+            replica: self.id.0,
+            slot: self.instance_number,
+            epoch: self.epoch,
         };
-        self.update_log(
-            log_entry,
-            &Instance {
+        self.instance_number += 1;
+        let footprint = footprint(&command, &reconfig);
+        let mut interf = self.find_interference(&footprint, &instance);
+        if reconfig.is_none() {
+            interf = self.union_deps(interf, self.to_collapse(&command));
+        }
+        let seq = 1 + self.find_max_seq(&footprint, &instance);
+        let payload = Payload {
+            command,
+            seq,
+            deps: interf,
+            instance,
+            noop: false,
+            // Every instance starts at the default ballot of its leader
+            ballot: Ballot {
+                epoch: self.epoch,
+                number: 0,
                 replica: self.id.0,
-                slot: slot,
             },
+            reconfig,
+        };
+        self.update_log(
+            LogEntry::from_payload(&payload, State::PreAccepted),
+            &payload.instance,
         );
-        Payload {
-            write_req: write_req,
-            seq: seq,
-            deps: interf,
-            instance: Instance {
-                replica: self.id.0,
-                slot: slot,
+        self.leading.insert(
+            payload.instance,
+            Leading {
+                payload: payload.clone(),
+                phase: Phase::PreAccept,
+                pre_accept_oks: Vec::new(),
+                // We accept our own proposal
+                accept_oks: 1,
             },
+        );
+        Ok(payload)
+    }
+
+    /// Handles a PreAcceptOK for an instance we lead. Once enough replies
+    /// are in, picks the fast or the slow path.
+    pub fn handle_pre_accept_ok(&mut self, pre_accept_ok: PreAcceptOKPayload<C>) -> Step<C> {
+        let instance = pre_accept_ok.payload.instance;
+        let leading = match self.leading.get_mut(&instance) {
+            Some(leading) if leading.phase == Phase::PreAccept => leading,
+            // A late reply for a phase we are already done with
+            _ => return Step::Wait,
+        };
+        if !pre_accept_ok.ok {
+            self.leading.remove(&instance);
+            return Step::Abort;
+        }
+        leading.pre_accept_oks.push(pre_accept_ok.payload);
+        if leading.pre_accept_oks.len() < self.fast_quorum - 1 {
+            return Step::Wait;
+        }
+        let pre_accept_oks = leading.pre_accept_oks.clone();
+        let payload = leading.payload.clone();
+        match self.decide_path(pre_accept_oks, &payload) {
+            Path::Fast(payload_) => {
+                self.leading.remove(&instance);
+                self.committed(payload_.clone());
+                Step::Commit(payload_)
+            }
+            Path::Slow(payload_) => self.start_accept(payload_),
+        }
+    }
+
+    /// Ends the PreAccept phase of an instance we lead once no more replies
+    /// will come in. Without a fast quorum, the replies of a slow quorum are
+    /// enough to go on through Paxos-Accept, so that we keep committing
+    /// while up to F replicas are down. Wait means too few replied.
+    pub fn end_pre_accept(&mut self, instance: &Instance) -> Step<C> {
+        let leading = match self.leading.get(instance) {
+            Some(leading) if leading.phase == Phase::PreAccept => leading,
+            _ => return Step::Wait,
+        };
+        if leading.pre_accept_oks.len() < self.slow_quorum - 1 {
+            return Step::Wait;
+        }
+        let pre_accept_oks = leading.pre_accept_oks.clone();
+        let payload = leading.payload.clone();
+        match self.decide_path(pre_accept_oks, &payload) {
+            Path::Fast(payload_) | Path::Slow(payload_) => self.start_accept(payload_),
         }
     }
 
-    pub fn decide_path(&self, pre_accept_oks: Vec<Payload>, payload: &Payload) -> Path {
+    // Moves an instance we lead on to Paxos-Accept with these attributes
+    fn start_accept(&mut self, payload: Payload<C>) -> Step<C> {
+        let instance = payload.instance;
+        if !self.accepted(payload.clone()) {
+            self.leading.remove(&instance);
+            return Step::Abort;
+        }
+        let leading = self.leading.get_mut(&instance).unwrap();
+        leading.phase = Phase::Accept;
+        leading.payload = payload.clone();
+        Step::Accept(payload)
+    }
+
+    /// Handles an AcceptOK for an instance we lead. Commits once a slow
+    /// quorum has accepted.
+    pub fn handle_accept_ok(&mut self, accept_ok: AcceptOKPayload<C>) -> Step<C> {
+        let instance = accept_ok.instance;
+        let leading = match self.leading.get_mut(&instance) {
+            Some(leading) if leading.phase == Phase::Accept => leading,
+            _ => return Step::Wait,
+        };
+        if let Some(committed) = accept_ok.committed {
+            // Recovered by another replica meanwhile. Unless it became a
+            // no-op, that is with our command.
+            self.leading.remove(&instance);
+            self.committed(committed.clone());
+            if committed.noop {
+                return Step::Abort;
+            }
+            return Step::Commit(committed);
+        }
+        if !accept_ok.ok {
+            self.leading.remove(&instance);
+            return Step::Abort;
+        }
+        leading.accept_oks += 1;
+        if leading.accept_oks < self.slow_quorum {
+            return Step::Wait;
+        }
+        let payload = leading.payload.clone();
+        self.leading.remove(&instance);
+        self.committed(payload.clone());
+        Step::Commit(payload)
+    }
+
+    // Gives up on an instance we lead, e.g. when its replies never came
+    pub fn stop_leading(&mut self, instance: &Instance) {
+        self.leading.remove(instance);
+    }
+
+    // The fast path needs a full fast quorum, ourselves included, that
+    // agrees with the seq and deps we proposed. Otherwise we go through
+    // Paxos-Accept with the union of all deps and the highest seq.
+    pub fn decide_path(&self, pre_accept_oks: Vec<Payload<C>>, payload: &Payload<C>) -> Path<C> {
         let mut new_payload = payload.clone();
-        let mut path = Path::Fast(payload.clone());
+        // We agree with ourselves
+        let mut agreeing = 1;
         for pre_accept_ok in pre_accept_oks {
-            let Payload {
-                write_req: _,
-                seq,
-                deps,
-                instance: _,
-            } = pre_accept_ok.clone();
-            if seq == payload.seq && deps == payload.deps {
+            if pre_accept_ok.seq == payload.seq && pre_accept_ok.deps == payload.deps {
+                agreeing += 1;
                 continue;
-            } else {
-                println!("Got some dissenting voice: {:#?}", pre_accept_ok.deps);
-                // Union deps from all replies
-                let new_deps = self.union_deps(new_payload.deps, pre_accept_ok.deps);
-                new_payload.deps = new_deps.clone();
-                // Set seq to max of seq from all replies
-                if pre_accept_ok.seq > seq {
-                    new_payload.seq = pre_accept_ok.seq;
-                }
-                path = Path::Slow(new_payload.clone());
             }
+            println!("Got some dissenting voice: {:#?}", pre_accept_ok.deps);
+            new_payload.deps = self.union_deps(new_payload.deps, pre_accept_ok.deps);
+            new_payload.seq = cmp::max(new_payload.seq, pre_accept_ok.seq);
+        }
+        if agreeing >= self.fast_quorum {
+            Path::Fast(payload.clone())
+        } else {
+            Path::Slow(new_payload)
         }
-        path
     }
 
-    pub fn committed(&mut self, payload: Payload) {
-        let Payload {
-            write_req,
-            seq,
-            deps,
-            instance,
-        } = payload;
-        self.instance_number += 1;
-        let log_entry = LogEntry {
-            key: write_req.key,
-            value: write_req.value,
-            seq: seq,
-            deps: deps,
-            state: State::Committed,
-        };
+    pub fn committed(&mut self, payload: Payload<C>) {
         self.update_log(
-            log_entry,
-            &Instance {
-                replica: instance.replica,
-                slot: instance.slot,
-            },
+            LogEntry::from_payload(&payload, State::Committed),
+            &payload.instance,
         );
         // println!("Commited. My log is {:#?}", self.cmds);
     }
 
-    pub fn accepted(&mut self, payload: Payload) {
-        let Payload {
-            write_req,
-            seq,
-            deps,
-            instance,
-        } = payload;
-        let log_entry = LogEntry {
-            key: write_req.key,
-            value: write_req.value,
-            seq: seq,
-            deps: deps,
-            state: State::Accepted,
-        };
+    // Returns false if another replica has taken over the instance meanwhile
+    pub fn accepted(&mut self, payload: Payload<C>) -> bool {
+        if self.is_stale(&payload.instance, payload.ballot, State::Accepted) {
+            return false;
+        }
         self.update_log(
-            log_entry,
-            &Instance {
-                replica: instance.replica,
-                slot: instance.slot,
-            },
+            LogEntry::from_payload(&payload, State::Accepted),
+            &payload.instance,
         );
+        true
+    }
+
+    /// Picks a ballot higher than any we have seen for the instance,
+    /// so that we can take it over from its command leader.
+    pub fn next_ballot(&self, instance: &Instance) -> Ballot {
+        let promised = self.promised(instance);
+        Ballot {
+            epoch: cmp::max(promised.epoch, self.epoch),
+            number: promised.number + 1,
+            replica: self.id.0,
+        }
+    }
+
+    /// Explicit Prepare as described in the paper. Among the replies, the
+    /// most advanced state wins: a committed command is simply committed
+    /// again, the accepted one with the highest ballot goes through
+    /// Paxos-Accept, and a command that was only pre-accepted is either
+    /// accepted (if F replicas other than the leader agree on its
+    /// attributes at the default ballot, as they would after a fast commit)
+    /// or pre-accepted again.
+    /// If nobody has seen the command, the instance is finished with a no-op.
+    /// `instance` may come from someone's deps, which do not carry the epoch
+    /// it was started in, so that comes from the replies.
+    pub fn decide_recovery(
+        &self,
+        instance: &Instance,
+        prepare_oks: &[PrepareOKPayload<C>],
+    ) -> Recovery<C> {
+        let entries: Vec<&(State, Payload<C>)> = prepare_oks
+            .iter()
+            .filter_map(|prepare_ok| prepare_ok.entry.as_ref())
+            .collect();
+        for (state, payload) in entries.iter() {
+            if *state == State::Committed || *state == State::Executed {
+                return Recovery::Commit(payload.clone());
+            }
+        }
+        let accepted = entries
+            .iter()
+            .filter(|(state, _)| *state == State::Accepted)
+            .map(|(_, payload)| payload)
+            .max_by_key(|payload| payload.ballot);
+        if let Some(payload) = accepted {
+            return Recovery::Accept(payload.clone());
+        }
+        // The ballot its leader started it with
+        let is_default_ballot = |payload: &Payload<C>| {
+            payload.ballot
+                == Ballot {
+                    epoch: payload.instance.epoch,
+                    number: 0,
+                    replica: instance.replica,
+                }
+        };
+        let pre_accepted: Vec<&Payload<C>> = prepare_oks
+            .iter()
+            .filter(|prepare_ok| prepare_ok.replica.0 != instance.replica)
+            .filter_map(|prepare_ok| prepare_ok.entry.as_ref())
+            .map(|(_, payload)| payload)
+            .filter(|payload| is_default_ballot(payload))
+            .collect();
+        for candidate in pre_accepted.iter() {
+            let identical = pre_accepted
+                .iter()
+                .filter(|payload| payload.seq == candidate.seq && payload.deps == candidate.deps)
+                .count();
+            if identical >= max_failures(self.replicas_num) {
+                return Recovery::Accept((*candidate).clone());
+            }
+        }
+        match entries.first() {
+            Some((_, payload)) => Recovery::PreAccept(payload.clone()),
+            // Started in the epoch of our recovery, as far as anyone knows
+            None => Recovery::Accept(Payload::noop(Instance {
+                epoch: prepare_oks
+                    .iter()
+                    .map(|prepare_ok| prepare_ok.ballot.epoch)
+                    .max()
+                    .unwrap_or(self.epoch),
+                ..*instance
+            })),
+        }
     }
 
     pub fn union_deps(&self, mut deps1: Vec<Instance>, mut deps2: Vec<Instance>) -> Vec<Instance> {
@@ -235,114 +908,315 @@ impl EpaxosLogic {
         deps1
     }
 
-    pub fn pre_accept_(&mut self, pre_accept_req: PreAccept) -> PreAcceptOK {
+    pub fn pre_accept_(&mut self, pre_accept_req: PreAccept<C>) -> PreAcceptOK<C> {
         let Payload {
-            write_req,
+            command,
             seq,
             mut deps,
             instance,
+            noop,
+            ballot,
+            reconfig,
         } = pre_accept_req.0;
-        let WriteRequest { key, value } = write_req.clone();
-        println!("Processing PreAccept for key: {}, value: {}", key, value);
-        let interf = self.find_interference(&key);
-        let seq_ = cmp::max(seq, 1 + self.find_max_seq(&interf));
-        if interf != deps {
-            deps = self.union_deps(deps, interf);
-        }
-        let log_entry = LogEntry {
-            key: key.to_owned(),
-            value: value,
+        println!("Processing PreAccept for {:?}", command);
+        if self.is_stale(&instance, ballot, State::PreAccepted) {
+            println!("NACK PreAccept for {:?} at ballot {:?}", instance, ballot);
+            return PreAcceptOK(PreAcceptOKPayload {
+                ok: false,
+                ballot: self.promised(&instance),
+                payload: Payload {
+                    command,
+                    seq,
+                    deps,
+                    instance,
+                    noop,
+                    ballot,
+                    reconfig,
+                },
+            });
+        }
+        let mut seq_ = seq;
+        if !noop {
+            let footprint = footprint(&command, &reconfig);
+            let interf = self.find_interference(&footprint, &instance);
+            seq_ = cmp::max(seq, 1 + self.find_max_seq(&footprint, &instance));
+            if interf != deps {
+                deps = self.union_deps(deps, interf);
+            }
+        }
+        let payload = Payload {
+            command,
             seq: seq_,
-            deps: deps.clone(),
-            state: State::PreAccepted,
+            deps,
+            instance,
+            noop,
+            ballot,
+            reconfig,
         };
-        self.update_log(log_entry, &instance);
-        PreAcceptOK(Payload {
-            write_req: write_req,
-            seq: seq_,
-            deps: deps,
-            instance: instance,
+        self.update_log(
+            LogEntry::from_payload(&payload, State::PreAccepted),
+            &instance,
+        );
+        PreAcceptOK(PreAcceptOKPayload {
+            ok: true,
+            ballot,
+            payload,
         })
     }
-    pub fn accept_(&mut self, accept_req: Accept) -> AcceptOK {
+    pub fn accept_(&mut self, accept_req: Accept<C>) -> AcceptOK<C> {
         println!("=======ACCEPT========");
-        let Payload {
-            write_req,
-            seq,
-            deps,
-            instance,
-        } = accept_req.0;
-        let WriteRequest { key, value } = write_req.clone();
-        let log_entry = LogEntry {
-            key: key,
-            value: value,
-            seq: seq,
-            deps: deps,
-            state: State::Accepted,
-        };
-        self.update_log(log_entry, &instance);
+        let payload = accept_req.0;
+        let committed = self
+            .get(&payload.instance)
+            .filter(|log_entry| log_entry.state >= State::Committed)
+            .map(|log_entry| Payload::from_log_entry(log_entry, payload.instance));
+        let ok = !self.is_stale(&payload.instance, payload.ballot, State::Accepted);
+        if ok {
+            self.update_log(
+                LogEntry::from_payload(&payload, State::Accepted),
+                &payload.instance,
+            );
+        } else {
+            println!(
+                "NACK Accept for {:?} at ballot {:?}",
+                payload.instance, payload.ballot
+            );
+        }
         AcceptOK(AcceptOKPayload {
-            write_req: write_req,
-            instance: instance,
+            ok,
+            ballot: self.promised(&payload.instance),
+            command: payload.command,
+            instance: payload.instance,
+            committed,
         })
     }
-    pub fn commit_(&mut self, commit_req: Commit) -> () {
-        let Payload {
-            write_req,
-            seq,
-            deps,
+    pub fn commit_(&mut self, commit_req: Commit<C>) {
+        let payload = commit_req.0;
+        // A committed command is final, so Commit needs no ballot check.
+        // Update the state in the log to commit
+        self.update_log(
+            LogEntry::from_payload(&payload, State::Committed),
+            &payload.instance,
+        );
+        println!("Committed {:?}", payload.instance);
+    }
+
+    pub fn prepare_(&mut self, prepare_req: Prepare) -> PrepareOK<C> {
+        let PreparePayload { ballot, instance } = prepare_req.0;
+        println!(
+            "Processing Prepare for {:?} with ballot {:?}",
+            instance, ballot
+        );
+        let promised = self.promised(&instance);
+        if ballot <= promised || ballot.epoch < self.epoch {
+            return PrepareOK(PrepareOKPayload {
+                ok: false,
+                ballot: promised,
+                replica: self.id,
+                instance,
+                entry: None,
+            });
+        }
+        self.persist(Record::Promise(PreparePayload { ballot, instance }));
+        self.ballots.insert(instance, ballot);
+        PrepareOK(PrepareOKPayload {
+            ok: true,
+            ballot,
+            replica: self.id,
             instance,
-        } = commit_req.0;
-        let log_entry = LogEntry {
-            key: write_req.key,
-            value: write_req.value,
-            seq: seq,
-            deps: deps,
-            state: State::Committed,
+            entry: self.get(&instance).map(|log_entry| {
+                (
+                    log_entry.state,
+                    Payload::from_log_entry(log_entry, instance),
+                )
+            }),
+        })
+    }
+
+    fn index_conflict(&mut self, log_entry: &LogEntry<C>, instance: &Instance) {
+        let replicas = self.cmds.len();
+        let replica = instance.replica as usize;
+        let conflict = Conflict {
+            slot: instance.slot,
+            seq: log_entry.seq,
         };
-        // Update the state in the log to commit
-        self.update_log(log_entry, &instance);
-        println!("Committed. My log is {:#?}", self.cmds);
-    }
-
-    fn find_interference(&self, key: &String) -> Vec<Instance> {
-        let mut interf = Vec::new();
-        for replica in 0..REPLICAS_NUM {
-            for (slot, log_entry) in self.cmds[replica].iter() {
-                if log_entry.key == *key {
-                    let instance = Instance {
-                        replica: replica as u32,
-                        slot: *slot as u32,
-                    };
-                    interf.push(instance);
+        if log_entry.reconfig.is_some() {
+            if self.reconfigs.len() < replicas {
+                self.reconfigs.resize(replicas, None);
+            }
+            let latest = &mut self.reconfigs[replica];
+            // A later phase of the same instance may have raised its seq
+            if latest.is_none_or(|latest| latest.slot <= instance.slot) {
+                *latest = Some(conflict);
+            }
+            return;
+        }
+        let command = &log_entry.command;
+        for key in command.keys() {
+            let rows = self.conflicts.entry(key.to_vec()).or_default();
+            if rows.len() < replicas {
+                rows.resize(replicas, KeyConflicts::default());
+            }
+            let row = &mut rows[replica];
+            // A committed command that depends on all of its replica's
+            // earlier ones on the key can stand in for them, like a barrier
+            let earlier: Vec<Conflict> = row
+                .barrier
+                .iter()
+                .chain(row.after.iter())
+                .filter(|earlier| earlier.slot < instance.slot)
+                .cloned()
+                .collect();
+            let collapses = log_entry.state == State::Committed
+                && earlier.len() >= MAX_KEY_CONFLICTS
+                && earlier.iter().all(|earlier| {
+                    log_entry
+                        .deps
+                        .contains(&Instance::at(instance.replica, earlier.slot))
+                });
+            if !command.is_barrier(key) && !collapses {
+                // A later barrier of the replica depends on it already
+                if row
+                    .barrier
+                    .is_some_and(|barrier| barrier.slot > instance.slot)
+                {
+                    continue;
                 }
+                row.after.retain(|after| after.slot != instance.slot);
+                row.after.push(conflict);
+            } else if row
+                .barrier
+                .is_none_or(|barrier| barrier.slot <= instance.slot)
+            {
+                row.barrier = Some(conflict);
+                row.after.retain(|after| after.slot > instance.slot);
             }
         }
-        interf.sort_by(sort_instances);
-        interf
     }
 
-    fn find_max_seq(&self, interf: &Vec<Instance>) -> u32 {
-        let mut seq = 0;
-        for instance in interf {
-            let interf_seq = self.cmds[instance.replica as usize]
-                .get(&(instance.slot as usize))
-                .unwrap()
-                .seq;
-            if interf_seq > seq {
-                seq = interf_seq;
+    // Our own instances that `command` should depend on, on the keys where
+    // they pile up, so that it can stand in for them once committed
+    fn to_collapse(&self, command: &C) -> Vec<Instance> {
+        let mut deps = Vec::new();
+        for key in command.keys() {
+            let row = self
+                .conflicts
+                .get(key)
+                .and_then(|rows| rows.get(self.id.0 as usize));
+            if let Some(row) = row.filter(|row| row.after.len() >= MAX_KEY_CONFLICTS) {
+                deps.extend(
+                    row.barrier
+                        .iter()
+                        .chain(row.after.iter())
+                        .map(|conflict| Instance::at(self.id.0, conflict.slot)),
+                );
+            }
+        }
+        deps
+    }
+
+    // The instances on one key that a command with this footprint depends
+    // on: the barrier of each replica, and the instances after it that it
+    // interferes with
+    fn key_conflicts(
+        &self,
+        rows: &[KeyConflicts],
+        footprint: &Footprint<C>,
+        interfering: &mut Vec<(u32, Conflict)>,
+    ) {
+        for (replica, row) in rows.iter().enumerate() {
+            let replica = replica as u32;
+            interfering.extend(row.barrier.map(|barrier| (replica, barrier)));
+            for after in &row.after {
+                let interferes = match footprint {
+                    Footprint::Command(command) => self
+                        .get(&Instance::at(replica, after.slot))
+                        // Without its command, e.g. once truncated, assume
+                        // it does
+                        .is_none_or(|log_entry| {
+                            log_entry.noop || log_entry.command.interferes(command)
+                        }),
+                    Footprint::Everything => true,
+                };
+                if interferes {
+                    interfering.push((replica, *after));
+                }
+            }
+        }
+    }
+
+    // The instances a command with this footprint interferes with, as
+    // (replica, conflict), leaving out those that others depend on already
+    fn interfering(&self, footprint: &Footprint<C>) -> Vec<(u32, Conflict)> {
+        let mut interfering: Vec<(u32, Conflict)> = self
+            .reconfigs
+            .iter()
+            .enumerate()
+            .filter_map(|(replica, conflict)| conflict.map(|conflict| (replica as u32, conflict)))
+            .collect();
+        match footprint {
+            Footprint::Command(command) => {
+                for key in command.keys() {
+                    if let Some(rows) = self.conflicts.get(key) {
+                        self.key_conflicts(rows, footprint, &mut interfering);
+                    }
+                }
+            }
+            Footprint::Everything => {
+                for rows in self.conflicts.values() {
+                    self.key_conflicts(rows, footprint, &mut interfering);
+                }
             }
         }
-        seq
+        interfering
+    }
+
+    // Instances other than `instance` that a command with this footprint
+    // depends on
+    fn find_interference(&self, footprint: &Footprint<C>, instance: &Instance) -> Vec<Instance> {
+        let mut interf: Vec<Instance> = self
+            .interfering(footprint)
+            .into_iter()
+            .map(|(replica, conflict)| Instance::at(replica, conflict.slot))
+            .filter(|dep| dep != instance)
+            .collect();
+        interf.sort_by(sort_instances);
+        interf.dedup();
+        interf
+    }
+
+    fn find_max_seq(&self, footprint: &Footprint<C>, instance: &Instance) -> u32 {
+        self.interfering(footprint)
+            .into_iter()
+            .filter(|(replica, conflict)| {
+                *replica != instance.replica || conflict.slot != instance.slot
+            })
+            .map(|(_, conflict)| conflict.seq)
+            .max()
+            .unwrap_or(0)
     }
 }
 
-impl fmt::Debug for LogEntry {
+impl<C: Replicated> fmt::Debug for LogEntry<C> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.noop {
+            return writeln!(
+                f,
+                "\nNoop\nSeq = {}\nDeps = {:#?}\nState = {:?}\nBallot = {:?}\n",
+                self.seq, self.deps, self.state, self.ballot
+            );
+        }
+        if let Some(reconfig) = &self.reconfig {
+            return writeln!(
+                f,
+                "\n{:?}\nSeq = {}\nDeps = {:#?}\nState = {:?}\nBallot = {:?}\n",
+                reconfig, self.seq, self.deps, self.state, self.ballot
+            );
+        }
         writeln!(
             f,
-            "\nWrite(key = {}, value = {})\nSeq = {}\nDeps = {:#?}\nState = {:?}\n",
-            self.key, self.value, self.seq, self.deps, self.state
+            "\n{:?}\nSeq = {}\nDeps = {:#?}\nState = {:?}\nBallot = {:?}\n",
+            self.command, self.seq, self.deps, self.state, self.ballot
         )
     }
 }
@@ -359,6 +1233,528 @@ impl fmt::Debug for State {
             State::PreAccepted => write!(f, "PreAccepted"),
             State::Accepted => write!(f, "Accepted"),
             State::Committed => write!(f, "Committed"),
+            State::Executed => write!(f, "Executed"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instance(replica: u32, slot: u32) -> Instance {
+        Instance::at(replica, slot)
+    }
+
+    fn cluster(replicas: u32) -> Topology {
+        Topology {
+            replicas: (0..replicas)
+                .map(|id| ReplicaConfig {
+                    id,
+                    host: LOCALHOST.to_string(),
+                    port: REPLICA_PORT + id as u16,
+                    region: "local".to_string(),
+                    quorum: Vec::new(),
+                    joining: false,
+                })
+                .collect(),
+        }
+    }
+
+    fn payload(seq: u32, deps: Vec<Instance>) -> Payload {
+        Payload {
+            command: Command::Put {
+                key: b"x".to_vec(),
+                value: b"1".to_vec(),
+            },
+            seq,
+            deps,
+            instance: instance(0, 7),
+            noop: false,
+            ballot: Ballot::default(),
+            reconfig: None,
+        }
+    }
+
+    #[test]
+    fn quorum_sizes_follow_cluster_size() {
+        assert_eq!(
+            (max_failures(3), slow_quorum_size(3), fast_quorum_size(3)),
+            (1, 2, 2)
+        );
+        assert_eq!(
+            (max_failures(5), slow_quorum_size(5), fast_quorum_size(5)),
+            (2, 3, 4)
+        );
+        assert_eq!(
+            (max_failures(7), slow_quorum_size(7), fast_quorum_size(7)),
+            (3, 4, 6)
+        );
+        assert_eq!(
+            (max_failures(9), slow_quorum_size(9), fast_quorum_size(9)),
+            (4, 5, 8)
+        );
+    }
+
+    #[test]
+    fn fast_path_when_a_fast_quorum_agrees() {
+        let logic = EpaxosLogic::init(ReplicaId(0), &cluster(5));
+        let proposal = payload(2, vec![instance(1, 0)]);
+        let replies = vec![payload(2, vec![instance(1, 0)]); logic.fast_quorum - 1];
+        match logic.decide_path(replies, &proposal) {
+            Path::Fast(payload_) => {
+                assert_eq!(payload_.seq, 2);
+                assert!(payload_.deps == vec![instance(1, 0)]);
+            }
+            Path::Slow(_) => panic!("expected the fast path"),
+        }
+    }
+
+    #[test]
+    fn slow_path_without_a_full_fast_quorum() {
+        let logic = EpaxosLogic::init(ReplicaId(0), &cluster(5));
+        let proposal = payload(1, vec![]);
+        let replies = vec![payload(1, vec![]); logic.fast_quorum - 2];
+        match logic.decide_path(replies, &proposal) {
+            Path::Slow(payload_) => assert_eq!(payload_.seq, 1),
+            Path::Fast(_) => panic!("too few replies for the fast path"),
+        }
+    }
+
+    #[test]
+    fn slow_path_takes_union_of_deps_and_max_seq() {
+        let logic = EpaxosLogic::init(ReplicaId(0), &cluster(5));
+        let proposal = payload(2, vec![instance(1, 0)]);
+        let replies = vec![
+            payload(5, vec![instance(1, 0), instance(2, 3)]),
+            payload(3, vec![instance(3, 1)]),
+        ];
+        match logic.decide_path(replies, &proposal) {
+            Path::Slow(payload_) => {
+                assert_eq!(payload_.seq, 5);
+                assert!(payload_.deps == vec![instance(1, 0), instance(2, 3), instance(3, 1)]);
+            }
+            Path::Fast(_) => panic!("replies disagree, expected the slow path"),
         }
     }
+
+    #[test]
+    fn differing_seq_alone_forces_slow_path() {
+        let logic = EpaxosLogic::init(ReplicaId(0), &cluster(5));
+        let proposal = payload(1, vec![]);
+        let mut replies = vec![payload(1, vec![]); logic.fast_quorum - 1];
+        replies[0].seq = 4;
+        match logic.decide_path(replies, &proposal) {
+            Path::Slow(payload_) => assert_eq!(payload_.seq, 4),
+            Path::Fast(_) => panic!("seq differs, expected the slow path"),
+        }
+    }
+
+    #[test]
+    fn extra_dissent_does_not_undo_an_agreeing_fast_quorum() {
+        let logic = EpaxosLogic::init(ReplicaId(0), &cluster(5));
+        let proposal = payload(1, vec![]);
+        let mut replies = vec![payload(1, vec![]); logic.fast_quorum - 1];
+        replies.push(payload(2, vec![instance(4, 0)]));
+        match logic.decide_path(replies, &proposal) {
+            Path::Fast(payload_) => {
+                assert_eq!(payload_.seq, 1);
+                assert!(payload_.deps.is_empty());
+            }
+            Path::Slow(_) => panic!("a fast quorum agreed, expected the fast path"),
+        }
+    }
+
+    #[test]
+    fn larger_clusters_need_larger_fast_quorums() {
+        let logic = EpaxosLogic::init(ReplicaId(0), &cluster(7));
+        let proposal = payload(1, vec![]);
+        // Enough for five replicas, not for seven
+        let replies = vec![payload(1, vec![]); 3];
+        match logic.decide_path(replies, &proposal) {
+            Path::Slow(_) => {}
+            Path::Fast(_) => panic!("four of seven is not a fast quorum"),
+        }
+        let replies = vec![payload(1, vec![]); 5];
+        match logic.decide_path(replies, &proposal) {
+            Path::Fast(_) => {}
+            Path::Slow(_) => panic!("six of seven is a fast quorum"),
+        }
+    }
+
+    fn prepare_ok(replica: u32, entry: Option<(State, Payload)>) -> PrepareOKPayload {
+        PrepareOKPayload {
+            ok: true,
+            ballot: Ballot {
+                epoch: 0,
+                number: 1,
+                replica: 3,
+            },
+            replica: ReplicaId(replica),
+            instance: instance(0, 7),
+            entry,
+        }
+    }
+
+    #[test]
+    fn recovery_keeps_what_a_fast_quorum_may_have_committed() {
+        let logic = EpaxosLogic::init(ReplicaId(3), &cluster(5));
+        // Replica 0 may have committed it on the fast path with replicas 1, 2
+        // and 3. Replica 3 recovers it and hears back from 1 and 4.
+        let proposal = payload(2, vec![instance(1, 0)]);
+        let prepare_oks = vec![
+            prepare_ok(3, Some((State::PreAccepted, proposal.clone()))),
+            prepare_ok(
+                4,
+                Some((State::PreAccepted, payload(3, vec![instance(4, 0)]))),
+            ),
+            prepare_ok(1, Some((State::PreAccepted, proposal.clone()))),
+        ];
+        match logic.decide_recovery(&instance(0, 7), &prepare_oks) {
+            Recovery::Accept(payload_) => {
+                assert_eq!(payload_.seq, 2);
+                assert!(payload_.deps == vec![instance(1, 0)]);
+            }
+            _ => panic!("expected the attributes of the fast quorum to be accepted"),
+        }
+    }
+
+    #[test]
+    fn recovery_pre_accepts_again_without_enough_identical_replies() {
+        let logic = EpaxosLogic::init(ReplicaId(3), &cluster(5));
+        let prepare_oks = vec![
+            prepare_ok(
+                3,
+                Some((State::PreAccepted, payload(2, vec![instance(1, 0)]))),
+            ),
+            prepare_ok(
+                4,
+                Some((State::PreAccepted, payload(3, vec![instance(4, 0)]))),
+            ),
+            prepare_ok(1, None),
+        ];
+        match logic.decide_recovery(&instance(0, 7), &prepare_oks) {
+            Recovery::PreAccept(_) => {}
+            _ => panic!("no fast quorum can have committed, expected PreAccept"),
+        }
+    }
+
+    #[test]
+    fn recovery_matches_default_ballots_of_later_epochs() {
+        let logic = EpaxosLogic::init(ReplicaId(3), &cluster(5));
+        // Started in epoch 1, and looked up through a dep, without its epoch
+        let mut proposal = payload(2, vec![instance(1, 0)]);
+        proposal.instance.epoch = 1;
+        proposal.ballot = Ballot {
+            epoch: 1,
+            number: 0,
+            replica: 0,
+        };
+        let prepare_oks = vec![
+            prepare_ok(3, Some((State::PreAccepted, proposal.clone()))),
+            prepare_ok(1, Some((State::PreAccepted, proposal.clone()))),
+            prepare_ok(4, None),
+        ];
+        match logic.decide_recovery(&instance(0, 7), &prepare_oks) {
+            Recovery::Accept(payload_) => {
+                assert_eq!(payload_.instance.epoch, 1);
+                assert_eq!(payload_.seq, 2);
+            }
+            _ => panic!("expected the attributes of the fast quorum to be accepted"),
+        }
+    }
+
+    #[test]
+    fn recovery_noop_takes_the_epoch_of_the_recovery() {
+        let logic = EpaxosLogic::init(ReplicaId(3), &cluster(5));
+        let mut prepare_oks = vec![
+            prepare_ok(3, None),
+            prepare_ok(4, None),
+            prepare_ok(1, None),
+        ];
+        for prepare_ok in prepare_oks.iter_mut() {
+            prepare_ok.ballot.epoch = 2;
+        }
+        match logic.decide_recovery(&instance(0, 7), &prepare_oks) {
+            Recovery::Accept(payload_) => {
+                assert!(payload_.noop);
+                assert_eq!(payload_.instance.epoch, 2);
+            }
+            _ => panic!("nobody saw the command, expected a no-op"),
+        }
+    }
+
+    fn get(key: &[u8]) -> Command {
+        Command::Get { key: key.to_vec() }
+    }
+
+    // Leads and commits `command` at replica 0 of three, returns its deps
+    fn lead(logic: &mut EpaxosLogic, command: Command) -> Vec<Instance> {
+        let payload = logic.lead_consensus(command, None).unwrap();
+        let deps = payload.deps.clone();
+        logic.committed(payload);
+        deps
+    }
+
+    #[test]
+    fn committed_reads_collapse_into_one_that_depends_on_them() {
+        let mut logic = EpaxosLogic::init(ReplicaId(0), &cluster(3));
+        for _ in 0..MAX_KEY_CONFLICTS {
+            assert!(lead(&mut logic, get(b"x")).is_empty());
+        }
+        let reads: Vec<Instance> = (0..MAX_KEY_CONFLICTS as u32)
+            .map(|slot| instance(0, slot))
+            .collect();
+        assert_eq!(lead(&mut logic, get(b"x")), reads);
+        let collapsed = &logic.conflicts[b"x".as_ref()][0];
+        assert!(collapsed.after.is_empty());
+        let put = Command::Put {
+            key: b"x".to_vec(),
+            value: b"1".to_vec(),
+        };
+        assert_eq!(
+            lead(&mut logic, put),
+            vec![instance(0, MAX_KEY_CONFLICTS as u32)]
+        );
+    }
+
+    #[test]
+    fn truncation_prunes_the_conflicts_index() {
+        let mut logic = EpaxosLogic::init(ReplicaId(0), &cluster(3));
+        for _ in 0..3 {
+            lead(&mut logic, get(b"x"));
+        }
+        lead(&mut logic, get(b"y"));
+        logic.truncate(&[2, 0, 0]).unwrap();
+        let rows = &logic.conflicts[b"x".as_ref()];
+        assert_eq!(
+            rows[0]
+                .after
+                .iter()
+                .map(|read| read.slot)
+                .collect::<Vec<_>>(),
+            vec![2]
+        );
+        logic.truncate(&[4, 0, 0]).unwrap();
+        assert!(logic.conflicts.is_empty());
+    }
+
+    // Commits `command` as the instance of another replica
+    fn commit_at(logic: &mut EpaxosLogic, at: Instance, command: Command) {
+        logic.commit_(Commit(Payload {
+            command,
+            instance: at,
+            ..payload(1, vec![])
+        }));
+    }
+
+    #[test]
+    fn interference_stops_at_the_barrier_of_each_replica() {
+        let mut logic = EpaxosLogic::init(ReplicaId(0), &cluster(3));
+        let put = |value: &[u8]| Command::Put {
+            key: b"x".to_vec(),
+            value: value.to_vec(),
+        };
+        let incr = Command::Increment {
+            key: b"x".to_vec(),
+            delta: 1,
+        };
+        commit_at(&mut logic, instance(1, 0), put(b"1"));
+        commit_at(&mut logic, instance(1, 1), put(b"2"));
+        commit_at(&mut logic, instance(1, 2), incr.clone());
+        commit_at(&mut logic, instance(1, 3), get(b"x"));
+        commit_at(&mut logic, instance(2, 0), incr.clone());
+        commit_at(&mut logic, instance(2, 1), incr.clone());
+        let own = instance(0, 0);
+        // Not (1, 0), which (1, 1) depends on, nor the read (1, 3)
+        let read = logic.find_interference(&Footprint::Command(&get(b"x")), &own);
+        assert_eq!(
+            read,
+            vec![
+                instance(1, 1),
+                instance(1, 2),
+                instance(2, 0),
+                instance(2, 1)
+            ]
+        );
+        // Increments commute with each other
+        let add = logic.find_interference(&Footprint::Command(&incr), &own);
+        assert_eq!(add, vec![instance(1, 1), instance(1, 3)]);
+    }
+
+    fn pre_accept_ok(payload: &Payload) -> PreAcceptOKPayload {
+        PreAcceptOKPayload {
+            ok: true,
+            ballot: payload.ballot,
+            payload: payload.clone(),
+        }
+    }
+
+    fn accept_ok(payload: &Payload) -> AcceptOKPayload {
+        AcceptOKPayload {
+            ok: true,
+            ballot: payload.ballot,
+            command: payload.command.clone(),
+            instance: payload.instance,
+            committed: None,
+        }
+    }
+
+    #[test]
+    fn slow_quorum_commits_with_f_replicas_down() {
+        let mut logic = EpaxosLogic::init(ReplicaId(0), &cluster(5));
+        let payload = logic.lead_consensus(get(b"x"), None).unwrap();
+        // Replicas 3 and 4 are down, so no fast quorum of 4 ever replies
+        for _ in 1..3 {
+            match logic.handle_pre_accept_ok(pre_accept_ok(&payload)) {
+                Step::Wait => {}
+                _ => panic!("two replies are not a fast quorum"),
+            }
+        }
+        let accepted = match logic.end_pre_accept(&payload.instance) {
+            Step::Accept(accepted) => accepted,
+            _ => panic!("a slow quorum replied, expected Accept"),
+        };
+        assert_eq!(accepted.deps, payload.deps);
+        assert!(matches!(
+            logic.handle_accept_ok(accept_ok(&accepted)),
+            Step::Wait
+        ));
+        match logic.handle_accept_ok(accept_ok(&accepted)) {
+            Step::Commit(committed) => assert_eq!(committed.instance, payload.instance),
+            _ => panic!("a slow quorum accepted, expected Commit"),
+        }
+    }
+
+    // Leads `get(b"x")` at replica 0 of five into Paxos-Accept, with the
+    // replies of a slow quorum
+    fn lead_to_accept(logic: &mut EpaxosLogic) -> Payload {
+        let payload = logic.lead_consensus(get(b"x"), None).unwrap();
+        for _ in 1..3 {
+            logic.handle_pre_accept_ok(pre_accept_ok(&payload));
+        }
+        match logic.end_pre_accept(&payload.instance) {
+            Step::Accept(accepted) => accepted,
+            _ => panic!("a slow quorum replied, expected Accept"),
+        }
+    }
+
+    #[test]
+    fn leader_learns_a_commit_from_an_accept_reply() {
+        let mut logic = EpaxosLogic::init(ReplicaId(0), &cluster(5));
+        let accepted = lead_to_accept(&mut logic);
+        // A recoverer committed it with our command and other deps
+        let recovered = Payload {
+            deps: vec![instance(3, 0)],
+            ..accepted.clone()
+        };
+        let reply = AcceptOKPayload {
+            ok: false,
+            committed: Some(recovered),
+            ..accept_ok(&accepted)
+        };
+        match logic.handle_accept_ok(reply) {
+            Step::Commit(committed) => assert_eq!(committed.deps, vec![instance(3, 0)]),
+            _ => panic!("it committed, expected Commit"),
+        }
+        assert_eq!(logic.state_of(&accepted.instance), Some(State::Committed));
+    }
+
+    #[test]
+    fn leader_fails_when_its_instance_committed_as_a_no_op() {
+        let mut logic = EpaxosLogic::init(ReplicaId(0), &cluster(5));
+        let accepted = lead_to_accept(&mut logic);
+        let reply = AcceptOKPayload {
+            ok: false,
+            committed: Some(Payload {
+                noop: true,
+                ..accepted.clone()
+            }),
+            ..accept_ok(&accepted)
+        };
+        assert!(matches!(logic.handle_accept_ok(reply), Step::Abort));
+        assert!(logic.get(&accepted.instance).unwrap().noop);
+        assert_eq!(logic.state_of(&accepted.instance), Some(State::Committed));
+    }
+
+    #[test]
+    fn pre_accept_fails_without_a_slow_quorum() {
+        let mut logic = EpaxosLogic::init(ReplicaId(0), &cluster(5));
+        let payload = logic.lead_consensus(get(b"x"), None).unwrap();
+        logic.handle_pre_accept_ok(pre_accept_ok(&payload));
+        assert!(matches!(
+            logic.end_pre_accept(&payload.instance),
+            Step::Wait
+        ));
+    }
+
+    fn at_ballot(mut payload: Payload, number: u32, replica: u32) -> Payload {
+        payload.ballot = Ballot {
+            epoch: 0,
+            number,
+            replica,
+        };
+        payload
+    }
+
+    fn state_and_seq(logic: &EpaxosLogic) -> (State, u32) {
+        let log_entry = logic.get(&instance(0, 7)).unwrap();
+        (log_entry.state, log_entry.seq)
+    }
+
+    #[test]
+    fn late_messages_do_not_undo_a_commit() {
+        let mut logic = EpaxosLogic::init(ReplicaId(1), &cluster(3));
+        logic.commit_(Commit(payload(2, vec![instance(2, 0)])));
+        let PreAcceptOK(reply) = logic.pre_accept_(PreAccept(payload(5, Vec::new())));
+        assert!(!reply.ok);
+        assert_eq!(state_and_seq(&logic), (State::Committed, 2));
+        // Even at a higher ballot, e.g. from a replica recovering it, which
+        // learns what it committed with instead
+        let AcceptOK(reply) = logic.accept_(Accept(at_ballot(payload(5, Vec::new()), 1, 2)));
+        assert!(!reply.ok);
+        let committed = reply.committed.unwrap();
+        assert_eq!(committed.seq, 2);
+        assert_eq!(committed.deps, vec![instance(2, 0)]);
+        assert_eq!(state_and_seq(&logic), (State::Committed, 2));
+        assert_eq!(
+            logic.get(&instance(0, 7)).unwrap().deps,
+            vec![instance(2, 0)]
+        );
+    }
+
+    #[test]
+    fn accept_at_a_lower_ballot_is_nacked() {
+        let mut logic = EpaxosLogic::init(ReplicaId(1), &cluster(3));
+        let AcceptOK(reply) = logic.accept_(Accept(at_ballot(payload(2, Vec::new()), 2, 0)));
+        assert!(reply.ok);
+        let AcceptOK(reply) = logic.accept_(Accept(at_ballot(payload(3, Vec::new()), 1, 2)));
+        assert!(!reply.ok);
+        // The NACK tells the sender which ballot we are bound to
+        assert_eq!(
+            reply.ballot,
+            Ballot {
+                epoch: 0,
+                number: 2,
+                replica: 0
+            }
+        );
+        assert_eq!(state_and_seq(&logic), (State::Accepted, 2));
+    }
+
+    #[test]
+    fn same_ballot_cannot_move_an_instance_back() {
+        let mut logic = EpaxosLogic::init(ReplicaId(1), &cluster(3));
+        let AcceptOK(reply) = logic.accept_(Accept(payload(2, Vec::new())));
+        assert!(reply.ok);
+        let PreAcceptOK(reply) = logic.pre_accept_(PreAccept(payload(3, Vec::new())));
+        assert!(!reply.ok);
+        assert_eq!(state_and_seq(&logic), (State::Accepted, 2));
+        // Nor can a record that goes straight to the log, e.g. one replayed
+        logic.update_log(
+            LogEntry::from_payload(&payload(3, Vec::new()), State::PreAccepted),
+            &instance(0, 7),
+        );
+        assert_eq!(state_and_seq(&logic), (State::Accepted, 2));
+    }
 }
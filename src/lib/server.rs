@@ -0,0 +1,1225 @@
+use crate::catch_up::{CatchUp, CatchUpRequest};
+use crate::command::Replicated;
+use crate::epaxos as grpc_service;
+use crate::epaxos_grpc::{EpaxosInternal, EpaxosInternalClient};
+use crate::logic::*;
+use crate::snapshot::{Snapshot, Watermarks};
+use crate::state_machine::{KvStore, StateMachine};
+use crate::topology::{ReplicaConfig, Topology};
+use crate::wal::Wal;
+use crossbeam::channel::{self, Receiver};
+use futures01::{future, Future};
+use futures_cpupool::CpuPool;
+use grpc::ClientStub;
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::{Arc, Condvar, Mutex, RwLock},
+    thread,
+    time::{Duration, Instant},
+};
+use tokio_timer::Timer;
+
+// Handlers block on the log, and client handlers also on the network, so
+// they must not run on the grpc event loop. Each one in flight occupies a
+// thread of its pool. Peer handlers get a pool of their own, so that clients
+// waiting on their peers cannot take every thread that would answer them.
+const CLIENT_THREADS: usize = 64;
+const PEER_THREADS: usize = 16;
+// Our calls to other replicas only wait on this pool to be polled, so a few
+// threads drive any number of them
+const RPC_THREADS: usize = 2;
+
+// How a call to another replica fails when it runs out of time
+const DEADLINE_EXCEEDED: &str = "deadline exceeded";
+
+// How long a client waits for the instance of its command to execute. Its
+// dependencies may need recovery first.
+const EXECUTE_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Replies to a broadcast in the order they arrive, until every call has
+// been answered or has run out of time. Failed calls are logged and skipped.
+struct Replies<R, T> {
+    stage: &'static str,
+    receiver: Receiver<(ReplicaId, grpc::Result<R>)>,
+    convert: fn(&R) -> T,
+    timed_out: bool,
+}
+
+impl<R, T> Replies<R, T> {
+    // Why we ran out of replies before reaching a decision
+    fn error(&self) -> EpaxosError {
+        if self.timed_out {
+            EpaxosError::Timeout(self.stage)
+        } else {
+            EpaxosError::NoQuorum(self.stage)
+        }
+    }
+}
+
+impl<R, T> Replies<R, T> {
+    // Like `next`, but also says who replied
+    fn next_from(&mut self) -> Option<(ReplicaId, T)> {
+        // Gone once every call has completed
+        while let Ok((replica_id, result)) = self.receiver.recv() {
+            match result {
+                Ok(value) => return Some((replica_id, (self.convert)(&value))),
+                Err(grpc::Error::Other(DEADLINE_EXCEEDED)) => {
+                    println!("[{}] Replica {} timed out", self.stage, replica_id.0);
+                    self.timed_out = true;
+                }
+                Err(e) => println!("[{}] Replica {} failed {:?}", self.stage, replica_id.0, e),
+            }
+        }
+        None
+    }
+}
+
+impl<R, T> Iterator for Replies<R, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.next_from().map(|(_, value)| value)
+    }
+}
+
+// Commits that peers have not acknowledged yet, see `retransmit_commits`
+#[derive(Default)]
+struct UnackedCommits<C> {
+    peers: HashMap<ReplicaId, PeerCommits>,
+    // The attributes of the instances some peer still has to acknowledge
+    payloads: HashMap<Instance, Payload<C>>,
+}
+
+#[derive(Default)]
+struct PeerCommits {
+    instances: HashSet<Instance>,
+    // Rounds of retries in a row it did not answer, and when the next is due
+    failures: u32,
+    retry_at: Option<Instant>,
+    // A round of retries to it has not finished yet
+    in_flight: bool,
+    // Counted as down: what it misses, it has to catch up on
+    down: bool,
+}
+
+impl<C: Replicated> UnackedCommits<C> {
+    fn add(&mut self, peer: ReplicaId, payload: &Payload<C>) {
+        let commits = self.peers.entry(peer).or_default();
+        if commits.down {
+            return;
+        }
+        commits.instances.insert(payload.instance);
+        self.payloads
+            .entry(payload.instance)
+            .or_insert_with(|| payload.clone());
+    }
+
+    fn ack(&mut self, peer: ReplicaId, instance: &Instance) {
+        if let Some(commits) = self.peers.get_mut(&peer) {
+            commits.instances.remove(instance);
+            commits.failures = 0;
+            commits.retry_at = None;
+            commits.down = false;
+        }
+        if self
+            .peers
+            .values()
+            .all(|commits| !commits.instances.contains(instance))
+        {
+            self.payloads.remove(instance);
+        }
+    }
+
+    // After a round of retries that the peer left unanswered
+    fn failed(&mut self, peer: ReplicaId) {
+        let commits = match self.peers.get_mut(&peer) {
+            Some(commits) => commits,
+            None => return,
+        };
+        commits.failures += 1;
+        if commits.failures >= COMMIT_RETRY_LIMIT {
+            println!(
+                "Replica {} seems down, dropping {} Commits it will have to catch up on",
+                peer.0,
+                commits.instances.len()
+            );
+            commits.down = true;
+            commits.instances.clear();
+        } else {
+            commits.retry_at =
+                Some(Instant::now() + COMMIT_RETRY_INTERVAL * (1 << commits.failures));
+        }
+        self.collect_garbage();
+    }
+
+    fn collect_garbage(&mut self) {
+        let peers = &self.peers;
+        self.payloads.retain(|instance, _| {
+            peers
+                .values()
+                .any(|commits| commits.instances.contains(instance))
+        });
+    }
+}
+
+// Commands we lead whose client waits for the result, from the moment we
+// start their instance until the client picks it up
+struct Results<O> {
+    waiting: HashSet<Instance>,
+    executed: HashMap<Instance, O>,
+}
+
+// Who we talk to in the current epoch. Rebuilt whenever we execute a
+// reconfiguration, see `refresh_members`.
+#[derive(Clone)]
+struct Members {
+    // Every other member. Any of them can make up our quorums, so a
+    // replica that is down does not stop us from committing.
+    peers: Vec<ReplicaId>,
+    // The peers we ask first, see `gather`. Empty means all of them.
+    quorum_peers: Vec<ReplicaId>,
+    // F + 1, ourselves included
+    slow_quorum: usize,
+}
+
+// Replicates the state machine S. Serves the EpaxosInternal service to the
+// other replicas; clients reach it through a service of the state machine's
+// own, such as the key-value one of the server binary.
+pub struct EpaxosServer<S: StateMachine = KvStore> {
+    id: ReplicaId,
+    // In grpc, parameters in service are immutable.
+    // See https://github.com/stepancheg/grpc-rust/blob/master/docs/FAQ.md
+    state: Arc<Mutex<S>>,
+    epaxos_logic: Arc<Mutex<EpaxosLogic<S::Command>>>,
+    // The write-ahead log behind epaxos_logic, see `durably`
+    wal: Wal,
+    snapshot_path: PathBuf,
+    // Every replica we know of, current members or not
+    replicas: Arc<RwLock<HashMap<ReplicaId, Arc<EpaxosInternalClient>>>>,
+    members: Arc<RwLock<Members>>,
+    // See `retransmit_commits`
+    unacked_commits: Arc<Mutex<UnackedCommits<S::Command>>>,
+    results: Arc<(Mutex<Results<S::Output>>, Condvar)>,
+    // Runs the handlers of client requests, see `spawn_client`
+    client_pool: CpuPool,
+    // Runs the handlers of requests from other replicas, see `spawn_peer`
+    peer_pool: CpuPool,
+    // Drives our calls to other replicas, see `on_reply`
+    rpc_pool: CpuPool,
+    // Fails the calls that run past `rpc_timeout`
+    timer: Timer,
+    // How long we wait for the replies of one round of RPCs
+    rpc_timeout: Duration,
+    // The largest encoded command a request may carry, in bytes
+    max_command_size: usize,
+}
+
+// Not derived, which would require S: Clone
+impl<S: StateMachine> Clone for EpaxosServer<S> {
+    fn clone(&self) -> Self {
+        EpaxosServer {
+            id: self.id,
+            state: self.state.clone(),
+            epaxos_logic: self.epaxos_logic.clone(),
+            wal: self.wal.clone(),
+            snapshot_path: self.snapshot_path.clone(),
+            replicas: self.replicas.clone(),
+            members: self.members.clone(),
+            unacked_commits: self.unacked_commits.clone(),
+            results: self.results.clone(),
+            client_pool: self.client_pool.clone(),
+            peer_pool: self.peer_pool.clone(),
+            rpc_pool: self.rpc_pool.clone(),
+            timer: self.timer.clone(),
+            rpc_timeout: self.rpc_timeout,
+            max_command_size: self.max_command_size,
+        }
+    }
+}
+
+impl<S: StateMachine> EpaxosServer<S> {
+    // `state` is where the state machine starts from without a snapshot
+    pub fn init(
+        id: ReplicaId,
+        mut state: S,
+        topology: &Topology,
+        wal_path: &str,
+        group_commit_wait: Duration,
+        rpc_timeout: Duration,
+        max_command_size: usize,
+    ) -> Self {
+        println!(
+            "Initializing Replica {} of {}",
+            id.0,
+            topology.members().len()
+        );
+        let mut epaxos_logic = EpaxosLogic::open(id, topology, wal_path, group_commit_wait)
+            .expect("Cannot open the write-ahead log");
+        let snapshot_path = PathBuf::from(wal_path).with_extension("snapshot");
+        if let Some(snapshot) = Snapshot::load(&snapshot_path).expect("Cannot load the snapshot") {
+            state
+                .restore(&snapshot.state)
+                .expect("Cannot restore the snapshot");
+            epaxos_logic.restore(&snapshot);
+        }
+        let server = EpaxosServer {
+            id,
+            state: Arc::new(Mutex::new(state)),
+            wal: epaxos_logic.wal().unwrap(),
+            snapshot_path,
+            epaxos_logic: Arc::new(Mutex::new(epaxos_logic)),
+            replicas: Arc::new(RwLock::new(HashMap::new())),
+            members: Arc::new(RwLock::new(Members {
+                peers: Vec::new(),
+                quorum_peers: Vec::new(),
+                slow_quorum: 0,
+            })),
+            unacked_commits: Arc::new(Mutex::new(UnackedCommits::default())),
+            results: Arc::new((
+                Mutex::new(Results {
+                    waiting: HashSet::new(),
+                    executed: HashMap::new(),
+                }),
+                Condvar::new(),
+            )),
+            client_pool: CpuPool::new(CLIENT_THREADS),
+            peer_pool: CpuPool::new(PEER_THREADS),
+            rpc_pool: CpuPool::new(RPC_THREADS),
+            timer: tokio_timer::wheel()
+                .tick_duration(Duration::from_millis(10))
+                .max_timeout(rpc_timeout)
+                .channel_capacity(1 << 16)
+                .build(),
+            rpc_timeout,
+            max_command_size,
+        };
+        for replica in topology.replicas.iter() {
+            server.connect(replica);
+        }
+        server.refresh_members();
+        // Whatever committed but is not in the snapshot is applied again, in
+        // the same order, to rebuild the state
+        server.execute();
+        server
+    }
+
+    // Starts the threads that recover, snapshot, retransmit and catch up in
+    // the background
+    pub fn start(&self) {
+        let recovery = self.clone();
+        thread::spawn(move || recovery.recover_stalled());
+        let snapshots = self.clone();
+        thread::spawn(move || snapshots.snapshot_periodically());
+        let commits = self.clone();
+        thread::spawn(move || commits.retransmit_commits());
+        let catch_up = self.clone();
+        thread::spawn(move || catch_up.catch_up_periodically());
+    }
+
+    fn connect(&self, replica: &ReplicaConfig) {
+        let mut replicas = self.replicas.write().unwrap();
+        if replica.id == self.id.0 || replicas.contains_key(&ReplicaId(replica.id)) {
+            return;
+        }
+        let internal_client =
+            grpc::Client::new_plain(&replica.host, replica.port, Default::default()).unwrap();
+        println!(
+            ">> Neighbor replica {} created : {}:{} ({})",
+            replica.id, replica.host, replica.port, replica.region
+        );
+        let client = EpaxosInternalClient::with_client(Arc::new(internal_client));
+        replicas.insert(ReplicaId(replica.id), Arc::new(client));
+    }
+
+    // Catches up with the replica set of the logic's current epoch
+    fn refresh_members(&self) {
+        let members = self.epaxos_logic.lock().unwrap().members.clone();
+        for replica in members.replicas.iter() {
+            self.connect(replica);
+        }
+        let peers: Vec<ReplicaId> = members
+            .ids()
+            .into_iter()
+            .filter(|replica_id| *replica_id != self.id)
+            .collect();
+        let quorum_peers = match members.get(self.id) {
+            Some(me) => me
+                .quorum
+                .iter()
+                .map(|peer| ReplicaId(*peer))
+                .filter(|peer| peers.contains(peer))
+                .collect(),
+            None => Vec::new(),
+        };
+        *self.members.write().unwrap() = Members {
+            peers,
+            quorum_peers,
+            slow_quorum: slow_quorum_size(members.len()),
+        };
+    }
+
+    fn members(&self) -> Members {
+        self.members.read().unwrap().clone()
+    }
+
+    // Runs `change` on the log and returns once everything it wrote is on
+    // disk, so nobody hears of a change that a crash could take back. The log
+    // is not locked while we wait, so concurrent callers share one fsync.
+    fn durably<T, F>(&self, change: F) -> T
+    where
+        F: FnOnce(&mut EpaxosLogic<S::Command>) -> T,
+    {
+        let (result, position) = {
+            let mut epaxos_logic = self.epaxos_logic.lock().unwrap();
+            let result = change(&mut epaxos_logic);
+            (result, self.wal.appended())
+        };
+        self.wal.sync(position);
+        result
+    }
+
+    // Runs the handler of a request from another replica on the peer pool
+    // and completes the response with its result. These handlers must never
+    // wait on the network, or replicas could end up waiting on each other.
+    fn spawn_peer<T, F>(&self, handler: F) -> grpc::SingleResponse<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(EpaxosServer<S>) -> T + Send + 'static,
+    {
+        let server = self.clone();
+        grpc::SingleResponse::no_metadata(
+            self.peer_pool
+                .spawn_fn(move || Ok::<_, grpc::Error>(handler(server))),
+        )
+    }
+
+    // Runs the handler of a client request on the client pool. The handler
+    // may wait on other replicas, and fail the call.
+    pub fn spawn_client<T, F>(&self, handler: F) -> grpc::SingleResponse<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(EpaxosServer<S>) -> grpc::Result<T> + Send + 'static,
+    {
+        let server = self.clone();
+        grpc::SingleResponse::no_metadata(self.client_pool.spawn_fn(move || handler(server)))
+    }
+
+    // we only need to do consensus for write req
+    // The log is only locked to record what a reply changed, never while
+    // waiting on the network, so many instances can be in flight at once.
+    // Returns the instance that committed. If `wait` is set, `execute`
+    // keeps the result of the command for `run`.
+    fn consensus(
+        &self,
+        command: &S::Command,
+        reconfig: Option<Reconfiguration>,
+        wait: bool,
+    ) -> Result<Instance, EpaxosError> {
+        let size = command.encode().len();
+        if size > self.max_command_size {
+            return Err(EpaxosError::CommandTooLarge {
+                size,
+                max: self.max_command_size,
+            });
+        }
+        println!("Starting consensus");
+        let payload = self.durably(|epaxos_logic| {
+            let payload = epaxos_logic.lead_consensus(command.clone(), reconfig)?;
+            if wait {
+                // Before anyone can execute it
+                self.results
+                    .0
+                    .lock()
+                    .unwrap()
+                    .waiting
+                    .insert(payload.instance);
+            }
+            Ok(payload)
+        })?;
+        match self.lead(&payload) {
+            Ok(payload_) => {
+                let mut targets = self.members().peers;
+                // A replica we add does not know it is a member yet
+                if let Some(Reconfiguration::Add(replica)) = &payload_.reconfig {
+                    let added = ReplicaId(replica.id);
+                    if added != self.id && !targets.contains(&added) {
+                        self.connect(replica);
+                        targets.push(added);
+                    }
+                }
+                self.send_commits(&targets, &payload_);
+                Ok(payload_.instance)
+            }
+            Err(e) => {
+                self.epaxos_logic
+                    .lock()
+                    .unwrap()
+                    .stop_leading(&payload.instance);
+                if wait {
+                    self.results
+                        .0
+                        .lock()
+                        .unwrap()
+                        .waiting
+                        .remove(&payload.instance);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    // Runs PreAccept and, if needed, Paxos-Accept for an instance we lead.
+    // Returns the attributes it committed with.
+    fn lead(&self, payload: &Payload<S::Command>) -> Result<Payload<S::Command>, EpaxosError> {
+        let step = match self.gather(
+            |targets| self.send_pre_accepts(targets, payload),
+            |pre_accept_ok| {
+                self.durably(|epaxos_logic| epaxos_logic.handle_pre_accept_ok(pre_accept_ok))
+            },
+        ) {
+            Ok(step) => step,
+            // No fast quorum, but a slow one may have replied
+            Err(e) => {
+                match self.durably(|epaxos_logic| epaxos_logic.end_pre_accept(&payload.instance)) {
+                    Step::Wait => return Err(e),
+                    step => step,
+                }
+            }
+        };
+        let payload_ = match step {
+            Step::Commit(payload_) => return Ok(payload_),
+            Step::Accept(payload_) => payload_,
+            _ => return Err(EpaxosError::Preempted),
+        };
+
+        // Start Paxos-Accept stage
+        let step = self.gather(
+            |targets| self.send_accepts(targets, &payload_),
+            |accept_ok| self.durably(|epaxos_logic| epaxos_logic.handle_accept_ok(accept_ok)),
+        )?;
+        match step {
+            Step::Commit(payload_) => Ok(payload_),
+            _ => Err(EpaxosError::Preempted),
+        }
+    }
+
+    // Feeds replies to `handle` until it reaches a decision. We ask our
+    // preferred quorum peers first, and the remaining peers only if those
+    // do not get us there before the deadline. Without preferred peers,
+    // the first replies from anyone make up the quorum.
+    fn gather<R, T, F, H>(&self, send: F, mut handle: H) -> Result<Step<S::Command>, EpaxosError>
+    where
+        F: Fn(&[ReplicaId]) -> Replies<R, T>,
+        H: FnMut(T) -> Step<S::Command>,
+    {
+        let members = self.members();
+        let mut rounds = vec![members.peers.clone()];
+        if !members.quorum_peers.is_empty() {
+            let rest: Vec<ReplicaId> = members
+                .peers
+                .iter()
+                .filter(|replica_id| !members.quorum_peers.contains(replica_id))
+                .cloned()
+                .collect();
+            rounds = vec![members.quorum_peers.clone()];
+            if !rest.is_empty() {
+                rounds.push(rest);
+            }
+        }
+        for (round, targets) in rounds.iter().enumerate() {
+            let mut replies = send(targets);
+            for reply in replies.by_ref() {
+                match handle(reply) {
+                    Step::Wait => continue,
+                    step => return Ok(step),
+                }
+            }
+            if round + 1 == rounds.len() {
+                return Err(replies.error());
+            }
+            println!(
+                "[{}] No quorum from our quorum peers, asking the others",
+                replies.stage
+            );
+        }
+        unreachable!()
+    }
+
+    // Takes over an instance whose command leader seems to have failed and
+    // drives it to commit, either with its original command or a no-op.
+    fn recover(&self, instance: Instance) -> Result<(), EpaxosError> {
+        println!("Starting recovery of {:?}", instance);
+        let members = self.members();
+        let slow_quorum = members.slow_quorum;
+        // The suspected leader is left out, the others are enough for a quorum
+        let targets: Vec<ReplicaId> = members
+            .peers
+            .iter()
+            .filter(|replica_id| replica_id.0 != instance.replica)
+            .cloned()
+            .collect();
+        let (prepare, prepare_ok) = self.durably(|epaxos_logic| {
+            let prepare = PreparePayload {
+                ballot: epaxos_logic.next_ballot(&instance),
+                instance,
+            };
+            let prepare_ok = epaxos_logic.prepare_(Prepare(prepare.clone()));
+            (prepare, prepare_ok.0)
+        });
+        let mut prepare_oks = vec![prepare_ok];
+        let mut replies = self.send_prepares(&targets, &prepare);
+        prepare_oks.extend(replies.by_ref().take(slow_quorum - 1));
+        if let Some(nack) = prepare_oks.iter().find(|prepare_ok| !prepare_ok.ok) {
+            println!(
+                "Recovery of {:?} preempted by ballot {:?}",
+                instance, nack.ballot
+            );
+            return Err(EpaxosError::Preempted);
+        }
+        if prepare_oks.len() < slow_quorum {
+            return Err(replies.error());
+        }
+
+        let recovery = self
+            .epaxos_logic
+            .lock()
+            .unwrap()
+            .decide_recovery(&instance, &prepare_oks);
+        let mut payload = match recovery {
+            Recovery::Commit(payload) => {
+                self.send_commits(&members.peers, &payload);
+                self.durably(|epaxos_logic| epaxos_logic.commit_(Commit(payload)));
+                self.execute();
+                return Ok(());
+            }
+            Recovery::Accept(payload) => payload,
+            Recovery::PreAccept(mut payload) => {
+                // Run PreAccept again but never take the fast path
+                payload.ballot = prepare.ballot;
+                let pre_accept_ok = self
+                    .durably(|epaxos_logic| epaxos_logic.pre_accept_(PreAccept(payload)))
+                    .0;
+                let mut replies = self.send_pre_accepts(&targets, &pre_accept_ok.payload);
+                let mut pre_accept_oks: Vec<PreAcceptOKPayload<S::Command>> =
+                    replies.by_ref().take(slow_quorum - 1).collect();
+                if pre_accept_oks.len() < slow_quorum - 1 {
+                    return Err(replies.error());
+                }
+                pre_accept_oks.push(pre_accept_ok.clone());
+                if pre_accept_oks.iter().any(|pre_accept_ok| !pre_accept_ok.ok) {
+                    println!("Recovery of {:?} preempted during PreAccept", instance);
+                    return Err(EpaxosError::Preempted);
+                }
+                let pre_accept_oks = pre_accept_oks
+                    .into_iter()
+                    .map(|pre_accept_ok| pre_accept_ok.payload)
+                    .collect();
+                match self
+                    .epaxos_logic
+                    .lock()
+                    .unwrap()
+                    .decide_path(pre_accept_oks, &pre_accept_ok.payload)
+                {
+                    Path::Fast(payload) | Path::Slow(payload) => payload,
+                }
+            }
+        };
+        payload.ballot = prepare.ballot;
+        if let Some(State::Committed) | Some(State::Executed) =
+            self.epaxos_logic.lock().unwrap().state_of(&instance)
+        {
+            // Somebody else finished it in the meantime
+            return Ok(());
+        }
+        let accept_ok = self.durably(|epaxos_logic| epaxos_logic.accept_(Accept(payload.clone())));
+        if !accept_ok.0.ok {
+            return Err(EpaxosError::Preempted);
+        }
+        let mut replies = self.send_accepts(&targets, &payload);
+        // Ourselves included
+        let mut accept_oks = 1;
+        let mut committed = None;
+        for accept_ok in replies.by_ref() {
+            if accept_ok.committed.is_some() {
+                committed = accept_ok.committed;
+                break;
+            }
+            if accept_ok.ok {
+                accept_oks += 1;
+                if accept_oks == slow_quorum {
+                    break;
+                }
+            }
+        }
+        let payload = match committed {
+            // It committed meanwhile, so we finish it the same way
+            Some(committed) => committed,
+            None if accept_oks >= slow_quorum => payload,
+            None => return Err(replies.error()),
+        };
+        self.send_commits(&members.peers, &payload);
+        self.durably(|epaxos_logic| epaxos_logic.commit_(Commit(payload)));
+        self.execute();
+        Ok(())
+    }
+
+    // Periodically snapshots the state, tells the other replicas how far we
+    // have executed and drops what all of them have executed
+    fn snapshot_periodically(&self) {
+        loop {
+            thread::sleep(SNAPSHOT_INTERVAL);
+            let (snapshot, watermarks) = {
+                // Same lock order as `execute`, so the state matches the log
+                let state = self.state.lock().unwrap();
+                let epaxos_logic = self.epaxos_logic.lock().unwrap();
+                (epaxos_logic.snapshot(&*state), epaxos_logic.watermarks())
+            };
+            let watermarks = watermarks.to_grpc();
+            self.broadcast(
+                "Executed Stage",
+                &self.members().peers,
+                move |replica| replica.executed(grpc::RequestOptions::new(), watermarks.clone()),
+                |_| (),
+            );
+            if let Err(e) = snapshot.save(&self.snapshot_path) {
+                println!("Cannot save a snapshot: {}", e);
+                continue;
+            }
+            if let Err(e) = self
+                .epaxos_logic
+                .lock()
+                .unwrap()
+                .truncate(&snapshot.truncated)
+            {
+                println!("Cannot compact the write-ahead log: {}", e);
+            }
+        }
+    }
+
+    // Periodically checks whether we missed instances that committed
+    // elsewhere, e.g. while we were down, and fetches them from a peer.
+    // Right after a restart we may have missed anything, so we ask anyway.
+    fn catch_up_periodically(&self) {
+        let mut next = 0;
+        let mut behind = true;
+        loop {
+            if behind || self.epaxos_logic.lock().unwrap().is_behind() {
+                next = self.catch_up_from_any(next);
+            }
+            behind = false;
+            thread::sleep(CATCH_UP_INTERVAL);
+        }
+    }
+
+    // Tries the peers in turn, starting with the `next`th, until one of
+    // them answers. Returns where to start next time.
+    fn catch_up_from_any(&self, mut next: usize) -> usize {
+        let peers = self.members().peers;
+        for _ in 0..peers.len() {
+            let peer = peers[next % peers.len()];
+            next += 1;
+            match self.catch_up(peer) {
+                Ok(()) => break,
+                Err(e) => println!("Catching up from replica {} failed: {}", peer.0, e),
+            }
+        }
+        next
+    }
+
+    // Joins the replica set: once we have caught up, we ask a member to
+    // commit our addition. We become a member when we execute it.
+    fn join(&self, reconfig: Reconfiguration) -> Result<(), EpaxosError> {
+        self.catch_up_from_any(0);
+        let request = reconfig.to_grpc();
+        let mut error = EpaxosError::NoQuorum("Join Stage");
+        for peer in self.members().peers {
+            let request = request.clone();
+            let mut replies = self.broadcast(
+                "Join Stage",
+                &[peer],
+                move |replica| replica.reconfigure(grpc::RequestOptions::new(), request.clone()),
+                WriteResponse::from_grpc,
+            );
+            match replies.next() {
+                Some(response) if response.commit => return Ok(()),
+                Some(_) => println!("Replica {} did not add us", peer.0),
+                None => error = replies.error(),
+            }
+        }
+        Err(error)
+    }
+
+    fn catch_up(&self, peer: ReplicaId) -> Result<(), EpaxosError> {
+        let request = self
+            .epaxos_logic
+            .lock()
+            .unwrap()
+            .catch_up_request()
+            .to_grpc();
+        let mut replies = self.broadcast(
+            "Catch-up Stage",
+            &[peer],
+            move |replica| replica.catch_up(grpc::RequestOptions::new(), request.clone()),
+            CatchUp::from_grpc,
+        );
+        let catch_up = replies.next().ok_or_else(|| replies.error())?;
+        self.install(catch_up);
+        self.execute();
+        Ok(())
+    }
+
+    // Takes in what a peer sent to catch us up
+    fn install(&self, catch_up: CatchUp<S::Command>) {
+        // Same lock order as `execute`, so the state matches the log
+        let mut state = self.state.lock().unwrap();
+        let mut epaxos_logic = self.epaxos_logic.lock().unwrap();
+        for payload in catch_up.committed {
+            epaxos_logic.commit_(Commit(payload));
+        }
+        // A snapshot on disk must not get ahead of our log
+        self.wal.sync(self.wal.appended());
+        let snapshot = match catch_up.snapshot {
+            Some(snapshot) => snapshot,
+            None => return,
+        };
+        if !epaxos_logic.can_restore(&snapshot) {
+            println!("Ignoring a snapshot that is older than our log");
+            return;
+        }
+        // Kept in case the snapshot cannot be saved
+        let previous = state.snapshot();
+        if let Err(e) = state.restore(&snapshot.state) {
+            println!("Cannot restore a snapshot: {}", e);
+            return;
+        }
+        if let Err(e) = snapshot.save(&self.snapshot_path) {
+            println!("Cannot save a snapshot: {}", e);
+            state
+                .restore(&previous)
+                .expect("Cannot restore our own snapshot");
+            return;
+        }
+        epaxos_logic.restore(&snapshot);
+        println!("Restored a snapshot up to {:?}", snapshot.watermarks);
+    }
+
+    // Periodically recovers instances that keep blocking execution
+    fn recover_stalled(&self) {
+        let mut suspects = HashSet::new();
+        loop {
+            thread::sleep(RECOVERY_TIMEOUT);
+            let blocking: HashSet<Instance> = self
+                .epaxos_logic
+                .lock()
+                .unwrap()
+                .blocking_instances()
+                .into_iter()
+                .collect();
+            for instance in blocking.intersection(&suspects) {
+                if let Err(e) = self.recover(*instance) {
+                    println!("Recovery of {:?} failed: {}", instance, e);
+                }
+            }
+            suspects = blocking;
+        }
+    }
+
+    // Sends a request to all targets at once. Replies come out as they
+    // arrive, so callers can move on as soon as they have a quorum and
+    // leave the stragglers behind.
+    fn broadcast<R, T, F>(
+        &self,
+        stage: &'static str,
+        targets: &[ReplicaId],
+        send: F,
+        convert: fn(&R) -> T,
+    ) -> Replies<R, T>
+    where
+        R: Send + 'static,
+        F: Fn(&EpaxosInternalClient) -> grpc::SingleResponse<R>,
+    {
+        let (sender, receiver) = channel::unbounded();
+        let replicas = self.replicas.read().unwrap();
+        for replica_id in targets.iter() {
+            let replica = match replicas.get(replica_id) {
+                Some(replica) => replica,
+                None => continue,
+            };
+            let sender = sender.clone();
+            let replica_id = *replica_id;
+            self.on_reply(send(replica).drop_metadata(), move |result| {
+                // The receiver is gone once the caller has its quorum
+                let _ = sender.send((replica_id, result));
+            });
+        }
+        Replies {
+            stage,
+            receiver,
+            convert,
+            timed_out: false,
+        }
+    }
+
+    // Drives a call to another replica on the RPC pool and hands its reply
+    // to `handle`, so that no thread sits waiting on a peer that hangs. A
+    // call still unanswered after `rpc_timeout` is dropped and fails with
+    // DEADLINE_EXCEEDED.
+    fn on_reply<R, F, H>(&self, call: F, handle: H)
+    where
+        R: Send + 'static,
+        F: Future<Item = R, Error = grpc::Error> + Send + 'static,
+        H: FnOnce(grpc::Result<R>) + Send + 'static,
+    {
+        // A timer that cannot take more sleepers expires them at once
+        let deadline = self
+            .timer
+            .sleep(self.rpc_timeout)
+            .then(|_| Err(grpc::Error::Other(DEADLINE_EXCEEDED)));
+        self.rpc_pool
+            .spawn(call.select(deadline).then(move |result| {
+                handle(result.map(|(reply, _)| reply).map_err(|(e, _)| e));
+                Ok::<(), ()>(())
+            }))
+            .forget();
+    }
+
+    fn send_prepares(
+        &self,
+        targets: &[ReplicaId],
+        prepare: &PreparePayload,
+    ) -> Replies<grpc_service::PrepareOKPayload, PrepareOKPayload<S::Command>> {
+        let prepare = prepare.to_grpc();
+        self.broadcast(
+            "Prepare Stage",
+            targets,
+            move |replica| replica.prepare(grpc::RequestOptions::new(), prepare.clone()),
+            PrepareOKPayload::from_grpc,
+        )
+    }
+
+    fn send_pre_accepts(
+        &self,
+        targets: &[ReplicaId],
+        payload: &Payload<S::Command>,
+    ) -> Replies<grpc_service::PreAcceptOKPayload, PreAcceptOKPayload<S::Command>> {
+        let payload = payload.to_grpc();
+        self.broadcast(
+            "PreAccept Stage",
+            targets,
+            move |replica| replica.pre_accept(grpc::RequestOptions::new(), payload.clone()),
+            PreAcceptOKPayload::from_grpc,
+        )
+    }
+
+    fn send_accepts(
+        &self,
+        targets: &[ReplicaId],
+        payload: &Payload<S::Command>,
+    ) -> Replies<grpc_service::AcceptOKPayload, AcceptOKPayload<S::Command>> {
+        let payload = payload.to_grpc();
+        self.broadcast(
+            "Paxos-Accept Stage",
+            targets,
+            move |replica| replica.accept(grpc::RequestOptions::new(), payload.clone()),
+            AcceptOKPayload::from_grpc,
+        )
+    }
+
+    // Sends Commit without waiting for the replies. Until a peer
+    // acknowledges it, `retransmit_commits` keeps sending it again.
+    fn send_commits(&self, targets: &[ReplicaId], payload: &Payload<S::Command>) {
+        println!("Sending Commit to replicas {:?}", targets);
+        {
+            let mut unacked_commits = self.unacked_commits.lock().unwrap();
+            for replica_id in targets {
+                unacked_commits.add(*replica_id, payload);
+            }
+        }
+        let replicas = self.replicas.read().unwrap();
+        for replica_id in targets {
+            let replica = match replicas.get(replica_id) {
+                Some(replica) => replica,
+                None => continue,
+            };
+            let response = replica.commit(grpc::RequestOptions::new(), payload.to_grpc());
+            let unacked_commits = self.unacked_commits.clone();
+            let replica_id = *replica_id;
+            let instance = payload.instance;
+            self.on_reply(response.drop_metadata(), move |result| {
+                if result.is_ok() {
+                    unacked_commits.lock().unwrap().ack(replica_id, &instance);
+                }
+            });
+        }
+    }
+
+    // Periodically sends Commits again to the peers that have not
+    // acknowledged them, until each of them has or tells us it executed
+    // them. Each peer gets at most COMMIT_RETRY_BATCH of them per round, and
+    // one that does not answer gets them less and less often, until we
+    // count it as down. Peers that are no longer members are forgotten.
+    fn retransmit_commits(&self) {
+        loop {
+            thread::sleep(COMMIT_RETRY_INTERVAL);
+            let members = self.members().peers;
+            let batches: Vec<(ReplicaId, Vec<Payload<S::Command>>)> = {
+                let epaxos_logic = self.epaxos_logic.lock().unwrap();
+                let mut unacked_commits = self.unacked_commits.lock().unwrap();
+                unacked_commits
+                    .peers
+                    .retain(|replica_id, _| members.contains(replica_id));
+                for (replica_id, commits) in unacked_commits.peers.iter_mut() {
+                    commits
+                        .instances
+                        .retain(|instance| !epaxos_logic.has_executed(*replica_id, instance));
+                }
+                unacked_commits.collect_garbage();
+                let now = Instant::now();
+                let UnackedCommits { peers, payloads } = &mut *unacked_commits;
+                peers
+                    .iter_mut()
+                    .filter(|(_, commits)| {
+                        !commits.instances.is_empty()
+                            && !commits.in_flight
+                            && commits.retry_at.is_none_or(|retry_at| retry_at <= now)
+                    })
+                    .map(|(replica_id, commits)| {
+                        commits.in_flight = true;
+                        let mut instances: Vec<Instance> =
+                            commits.instances.iter().cloned().collect();
+                        instances.sort_by(sort_instances);
+                        instances.truncate(COMMIT_RETRY_BATCH);
+                        let batch = instances
+                            .iter()
+                            .map(|instance| payloads[instance].clone())
+                            .collect();
+                        (*replica_id, batch)
+                    })
+                    .collect()
+            };
+            for (replica_id, batch) in batches {
+                self.retransmit(replica_id, batch);
+            }
+        }
+    }
+
+    // Sends a peer a batch of Commits again, and takes stock once all of
+    // them have been answered
+    fn retransmit(&self, replica_id: ReplicaId, batch: Vec<Payload<S::Command>>) {
+        println!(
+            "Sending {} Commits again to replica {}",
+            batch.len(),
+            replica_id.0
+        );
+        let replica = self.replicas.read().unwrap().get(&replica_id).cloned();
+        let calls: Vec<_> = match replica {
+            Some(replica) => batch
+                .iter()
+                .map(|payload| {
+                    let instance = payload.instance;
+                    replica
+                        .commit(grpc::RequestOptions::new(), payload.to_grpc())
+                        .drop_metadata()
+                        .then(move |result| Ok(result.ok().map(|_| instance)))
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        let unacked_commits = self.unacked_commits.clone();
+        self.on_reply(future::join_all(calls), move |result| {
+            let acked: Vec<Instance> = result.unwrap_or_default().into_iter().flatten().collect();
+            let mut unacked_commits = unacked_commits.lock().unwrap();
+            if acked.is_empty() {
+                unacked_commits.failed(replica_id);
+            }
+            for instance in acked.iter() {
+                unacked_commits.ack(replica_id, instance);
+            }
+            if let Some(commits) = unacked_commits.peers.get_mut(&replica_id) {
+                commits.in_flight = false;
+            }
+        });
+    }
+
+    fn execute(&self) {
+        // Hold the state for the whole round so that two concurrent rounds
+        // cannot interleave their writes out of dependency order.
+        let mut state = self.state.lock().unwrap();
+        let executed = self.epaxos_logic.lock().unwrap().execute();
+        let mut reconfigured = false;
+        let mut waited_for = false;
+        for (instance, log_entry) in executed {
+            println!("Executing {:?}", log_entry);
+            if log_entry.reconfig.is_some() {
+                reconfigured = true;
+            } else if !log_entry.noop {
+                let result = state.apply(&log_entry.command);
+                let mut results = self.results.0.lock().unwrap();
+                if results.waiting.remove(&instance) {
+                    results.executed.insert(instance, result);
+                    waited_for = true;
+                }
+            }
+        }
+        if waited_for {
+            self.results.1.notify_all();
+        }
+        if reconfigured {
+            self.refresh_members();
+        }
+    }
+
+    // Commits `command` without waiting for its result
+    pub fn propose(&self, command: &S::Command) -> Result<(), EpaxosError> {
+        let result = self.consensus(command, None, false).map(|_| ());
+        self.execute();
+        result
+    }
+
+    // Runs `read` on the state as this replica has executed it so far,
+    // which may miss commands that committed elsewhere
+    pub fn read_local<T, F>(&self, read: F) -> T
+    where
+        F: FnOnce(&mut S) -> T,
+    {
+        self.execute();
+        read(&mut self.state.lock().unwrap())
+    }
+
+    // Commits `command` and returns its result once it executed, which
+    // reflects every command that committed before it started, wherever it
+    // was led.
+    pub fn run(&self, command: S::Command) -> Result<S::Output, EpaxosError> {
+        let instance = self.consensus(&command, None, true)?;
+        self.execute();
+        let (results, executed) = &*self.results;
+        let deadline = Instant::now() + EXECUTE_TIMEOUT;
+        let mut results = results.lock().unwrap();
+        loop {
+            if let Some(result) = results.executed.remove(&instance) {
+                return Ok(result);
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                results.waiting.remove(&instance);
+                return Err(EpaxosError::Timeout("Execute"));
+            }
+            results = executed.wait_timeout(results, deadline - now).unwrap().0;
+        }
+    }
+}
+
+impl<S: StateMachine> EpaxosInternal for EpaxosServer<S> {
+    fn pre_accept(
+        &self,
+        _o: grpc::RequestOptions,
+        p: grpc_service::Payload,
+    ) -> grpc::SingleResponse<grpc_service::PreAcceptOKPayload> {
+        println!("Received PreAccept");
+        self.spawn_peer(move |server| {
+            let request = PreAccept(Payload::from_grpc(&p));
+            let response = server.durably(|epaxos_logic| epaxos_logic.pre_accept_(request));
+            response.0.to_grpc()
+        })
+    }
+
+    fn accept(
+        &self,
+        _o: grpc::RequestOptions,
+        p: grpc_service::Payload,
+    ) -> grpc::SingleResponse<grpc_service::AcceptOKPayload> {
+        self.spawn_peer(move |server| {
+            let request = Accept(Payload::from_grpc(&p));
+            let response = server.durably(|epaxos_logic| epaxos_logic.accept_(request));
+            response.0.to_grpc()
+        })
+    }
+
+    fn commit(
+        &self,
+        _o: grpc::RequestOptions,
+        p: grpc_service::Payload,
+    ) -> grpc::SingleResponse<grpc_service::Empty> {
+        self.spawn_peer(move |server| {
+            let request = Commit(Payload::from_grpc(&p));
+            server.durably(|epaxos_logic| epaxos_logic.commit_(request));
+            server.execute();
+            grpc_service::Empty::new()
+        })
+    }
+
+    fn prepare(
+        &self,
+        _o: grpc::RequestOptions,
+        p: grpc_service::PreparePayload,
+    ) -> grpc::SingleResponse<grpc_service::PrepareOKPayload> {
+        self.spawn_peer(move |server| {
+            let request = Prepare(PreparePayload::from_grpc(&p));
+            let response = server.durably(|epaxos_logic| epaxos_logic.prepare_(request));
+            response.0.to_grpc()
+        })
+    }
+
+    fn executed(
+        &self,
+        _o: grpc::RequestOptions,
+        p: grpc_service::Watermarks,
+    ) -> grpc::SingleResponse<grpc_service::Empty> {
+        self.spawn_peer(move |server| {
+            server
+                .epaxos_logic
+                .lock()
+                .unwrap()
+                .record_watermarks(Watermarks::from_grpc(&p));
+            grpc_service::Empty::new()
+        })
+    }
+
+    fn catch_up(
+        &self,
+        _o: grpc::RequestOptions,
+        p: grpc_service::CatchUpRequest,
+    ) -> grpc::SingleResponse<grpc_service::CatchUpReply> {
+        self.spawn_peer(move |server| {
+            let request = CatchUpRequest::from_grpc(&p);
+            // Same lock order as `execute`, so a snapshot matches the log
+            let state = server.state.lock().unwrap();
+            let epaxos_logic = server.epaxos_logic.lock().unwrap();
+            epaxos_logic.catch_up(&request, &*state).to_grpc()
+        })
+    }
+
+    fn reconfigure(
+        &self,
+        _o: grpc::RequestOptions,
+        req: grpc_service::Reconfiguration,
+    ) -> grpc::SingleResponse<grpc_service::WriteResponse> {
+        self.spawn_client(move |server| {
+            let reconfig = Reconfiguration::from_grpc(&req);
+            println!("Received {:?}", reconfig);
+            let joining = match &reconfig {
+                Reconfiguration::Add(replica) => {
+                    replica.id == server.id.0 && !server.epaxos_logic.lock().unwrap().is_member()
+                }
+                Reconfiguration::Remove(_) => false,
+            };
+            let result = if joining {
+                server.join(reconfig)
+            } else {
+                let result = server
+                    .consensus(&S::Command::default(), Some(reconfig), false)
+                    .map(|_| ());
+                server.execute();
+                result
+            };
+            match result {
+                Ok(()) => Ok(WriteResponse { commit: true }.to_grpc()),
+                Err(e) => {
+                    println!("Reconfiguration failed: {}", e);
+                    Err(e.to_grpc())
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,412 @@
+use crate::command::Replicated;
+use crate::epaxos as grpc;
+use crate::logic::*;
+use crate::state_machine::StateMachine;
+use crate::topology::Topology;
+use crate::wal::{checksum, sync_dir, Record};
+use protobuf::Message;
+use std::{
+    cmp,
+    collections::HashSet,
+    fs::{self, File},
+    io::{self, Write},
+    path::Path,
+};
+
+// The state machine as of some point in the execution order, and the
+// instances that went into it
+pub struct Snapshot {
+    pub state: Vec<u8>,
+    // Every slot below watermarks[r] of replica r...
+    pub watermarks: Vec<u32>,
+    // ...and these, which were executed ahead of the watermarks
+    pub executed: Vec<Instance>,
+    // Slots below truncated[r] are gone from the log, see `truncate`
+    pub truncated: Vec<u32>,
+    // The replica set after the executed instances
+    pub epoch: u32,
+    pub members: Topology,
+}
+
+// How far a replica has executed, see `EpaxosLogic::watermarks`
+pub struct Watermarks {
+    pub replica: ReplicaId,
+    pub executed: Vec<u32>,
+}
+
+impl Snapshot {
+    /// Writes the snapshot to `path`, atomically replacing an older one
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        let body = self.to_grpc().write_to_bytes().unwrap();
+        let saving = path.with_extension("saving");
+        let mut file = File::create(&saving)?;
+        file.write_all(&checksum(&body).to_le_bytes())?;
+        file.write_all(&body)?;
+        file.sync_all()?;
+        fs::rename(&saving, path)?;
+        sync_dir(path)
+    }
+
+    /// Reads the snapshot at `path`, if we ever saved one
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Option<Snapshot>> {
+        let bytes = match fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        let corrupt = || io::Error::new(io::ErrorKind::InvalidData, "corrupt snapshot");
+        if bytes.len() < 4 {
+            return Err(corrupt());
+        }
+        let mut crc = [0; 4];
+        crc.copy_from_slice(&bytes[0..4]);
+        if checksum(&bytes[4..]) != u32::from_le_bytes(crc) {
+            return Err(corrupt());
+        }
+        let snapshot =
+            protobuf::parse_from_bytes::<grpc::Snapshot>(&bytes[4..]).map_err(|_| corrupt())?;
+        Ok(Some(Snapshot::from_grpc(&snapshot)))
+    }
+}
+
+impl<C: Replicated> EpaxosLogic<C> {
+    // Moves our watermarks past whatever has been executed right above them
+    pub fn advance_watermarks(&mut self) {
+        for replica in 0..self.cmds.len() {
+            loop {
+                let next = Instance::at(replica as u32, self.watermarks[replica]);
+                if self.state_of(&next) != Some(State::Executed) {
+                    break;
+                }
+                self.watermarks[replica] += 1;
+            }
+        }
+    }
+
+    /// What we tell the other replicas about our progress
+    pub fn watermarks(&self) -> Watermarks {
+        Watermarks {
+            replica: self.id,
+            executed: self.watermarks.clone(),
+        }
+    }
+
+    pub fn record_watermarks(&mut self, watermarks: Watermarks) {
+        if watermarks.replica == self.id || !self.members.contains(watermarks.replica) {
+            return;
+        }
+        // A peer that has not executed a reconfiguration yet may not know
+        // of the newest rows, and has executed nothing there
+        let mut executed = watermarks.executed;
+        self.ensure_rows(executed.len());
+        executed.resize(self.cmds.len(), 0);
+        self.peer_watermarks.insert(watermarks.replica, executed);
+    }
+
+    /// Whether `replica` told us it has executed the instance
+    pub fn has_executed(&self, replica: ReplicaId, instance: &Instance) -> bool {
+        self.peer_watermarks
+            .get(&replica)
+            .and_then(|executed| executed.get(instance.replica as usize))
+            .is_some_and(|watermark| instance.slot < *watermark)
+    }
+
+    /// Per replica, the slot below which every member has executed
+    /// everything. Until we have heard from all of them, nothing moves.
+    pub fn truncation_point(&self) -> Vec<u32> {
+        let heard_from_all = self
+            .members
+            .ids()
+            .iter()
+            .all(|member| *member == self.id || self.peer_watermarks.contains_key(member));
+        if !heard_from_all {
+            return self.truncated.clone();
+        }
+        let mut point = self.watermarks.clone();
+        for executed in self.peer_watermarks.values() {
+            for (replica, watermark) in executed.iter().enumerate().take(point.len()) {
+                point[replica] = cmp::min(point[replica], *watermark);
+            }
+        }
+        for (replica, truncated) in self.truncated.iter().enumerate() {
+            point[replica] = cmp::max(point[replica], *truncated);
+        }
+        point
+    }
+
+    /// Captures `state`, which must hold exactly the instances we have
+    /// executed so far
+    pub fn snapshot<S: StateMachine>(&self, state: &S) -> Snapshot {
+        let mut executed = Vec::new();
+        for (replica, log) in self.cmds.iter().enumerate() {
+            for (slot, log_entry) in log.iter() {
+                if log_entry.state == State::Executed && *slot as u32 >= self.watermarks[replica] {
+                    executed.push(Instance::at(replica as u32, *slot as u32));
+                }
+            }
+        }
+        executed.sort_by(sort_instances);
+        Snapshot {
+            state: state.snapshot(),
+            watermarks: self.watermarks.clone(),
+            executed,
+            truncated: self.truncation_point(),
+            epoch: self.epoch,
+            members: self.members.clone(),
+        }
+    }
+
+    /// Picks up from a snapshot, either once the write-ahead log has been
+    /// replayed or when a peer sent it to catch us up. Instances the
+    /// snapshot covers count as executed, so they are not applied to the
+    /// store a second time. Those it does not cover count as committed, so
+    /// they are applied to its store again.
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        self.ensure_rows(snapshot.watermarks.len());
+        let mut watermarks = snapshot.watermarks.clone();
+        watermarks.resize(self.cmds.len(), 0);
+        self.drop_below(&snapshot.truncated);
+        let executed: HashSet<&Instance> = snapshot.executed.iter().collect();
+        for (replica, log) in self.cmds.iter_mut().enumerate() {
+            for (slot, log_entry) in log.iter_mut() {
+                let instance = Instance::at(replica as u32, *slot as u32);
+                if instance.slot < watermarks[replica] || executed.contains(&instance) {
+                    log_entry.state = State::Executed;
+                } else if log_entry.state == State::Executed {
+                    log_entry.state = State::Committed;
+                }
+                if log_entry.state == State::Committed {
+                    self.unexecuted.insert(instance);
+                } else {
+                    self.unexecuted.remove(&instance);
+                }
+            }
+        }
+        self.watermarks = watermarks;
+        self.advance_watermarks();
+        // Reconfigurations the snapshot does not cover run again from here
+        if !snapshot.members.is_empty() {
+            self.epoch = snapshot.epoch;
+            self.set_members(snapshot.members.clone());
+        }
+        // Our own instances below the watermark may all be gone from the log
+        let id = self.id.0 as usize;
+        self.instance_number = cmp::max(self.instance_number, self.watermarks[id]);
+    }
+
+    /// Forgets every instance below `upto` and rewrites the write-ahead log
+    /// without them. A durable snapshot must already cover them.
+    pub fn truncate(&mut self, upto: &[u32]) -> io::Result<()> {
+        if upto
+            .iter()
+            .zip(self.truncated.iter())
+            .all(|(upto, truncated)| upto <= truncated)
+        {
+            return Ok(());
+        }
+        self.drop_below(upto);
+        println!("Truncated the log below {:?}", self.truncated);
+        match self.wal() {
+            Some(wal) => wal.compact(&self.live_records()),
+            None => Ok(()),
+        }
+    }
+
+    fn drop_below(&mut self, upto: &[u32]) {
+        for (replica, log) in self.cmds.iter_mut().enumerate() {
+            let upto = cmp::max(
+                upto.get(replica).cloned().unwrap_or(0),
+                self.truncated[replica],
+            );
+            log.retain(|slot, _| *slot as u32 >= upto);
+            self.truncated[replica] = upto;
+        }
+        let truncated = &self.truncated;
+        self.ballots
+            .retain(|instance, _| instance.slot >= truncated[instance.replica as usize]);
+        self.unexecuted
+            .retain(|instance| instance.slot >= truncated[instance.replica as usize]);
+        // Every replica executed them, so no new command has to depend on them
+        let is_live = |replica: usize, conflict: &Conflict| conflict.slot >= truncated[replica];
+        self.conflicts.retain(|_, rows| {
+            for (replica, row) in rows.iter_mut().enumerate() {
+                row.barrier = row.barrier.filter(|barrier| is_live(replica, barrier));
+                row.after.retain(|after| is_live(replica, after));
+            }
+            rows.iter()
+                .any(|row| row.barrier.is_some() || !row.after.is_empty())
+        });
+        for (replica, reconfig) in self.reconfigs.iter_mut().enumerate() {
+            *reconfig = reconfig.filter(|reconfig| is_live(replica, reconfig));
+        }
+    }
+
+    // Everything the write-ahead log must still hold
+    fn live_records(&self) -> Vec<Record<C>> {
+        let mut records = Vec::new();
+        for (replica, log) in self.cmds.iter().enumerate() {
+            for (slot, log_entry) in log.iter() {
+                let instance = Instance::at(replica as u32, *slot as u32);
+                records.push(Record::Entry(instance, log_entry.clone()));
+            }
+        }
+        for (instance, ballot) in self.ballots.iter() {
+            records.push(Record::Promise(PreparePayload {
+                ballot: *ballot,
+                instance: *instance,
+            }));
+        }
+        records
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::Command;
+    use crate::state_machine::KvStore;
+    use std::{env, process};
+
+    fn logic() -> EpaxosLogic {
+        EpaxosLogic::init(ReplicaId(0), &Topology::ec2())
+    }
+
+    fn put(logic: &mut EpaxosLogic, instance: Instance, state: State) {
+        let log_entry = LogEntry {
+            command: Command::Put {
+                key: format!("key{}", instance.slot).into_bytes(),
+                value: b"1".to_vec(),
+            },
+            seq: 1,
+            deps: vec![],
+            state,
+            noop: false,
+            ballot: Ballot::default(),
+            epoch: 0,
+            reconfig: None,
+        };
+        logic.update_log(log_entry, &instance);
+    }
+
+    fn watermarks(replica: u32, executed: Vec<u32>) -> Watermarks {
+        Watermarks {
+            replica: ReplicaId(replica),
+            executed,
+        }
+    }
+
+    fn snapshot(watermarks: Vec<u32>, executed: Vec<Instance>, truncated: Vec<u32>) -> Snapshot {
+        Snapshot {
+            state: Vec::new(),
+            watermarks,
+            executed,
+            truncated,
+            epoch: 0,
+            members: Topology {
+                replicas: Vec::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn watermarks_stop_at_the_first_unexecuted_slot() {
+        let mut logic = logic();
+        for slot in [0, 1, 3].iter() {
+            put(&mut logic, Instance::at(0, *slot), State::Executed);
+        }
+        put(&mut logic, Instance::at(0, 2), State::Committed);
+        logic.advance_watermarks();
+        assert_eq!(logic.watermarks[0], 2);
+        // As executing it would
+        logic.cmds[0].get_mut(&2).unwrap().state = State::Executed;
+        logic.advance_watermarks();
+        assert_eq!(logic.watermarks().executed, vec![4, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn truncation_point_waits_for_every_member() {
+        let mut logic = logic();
+        logic.watermarks = vec![5, 4, 3, 2, 1];
+        for replica in 1..4 {
+            logic.record_watermarks(watermarks(replica, vec![9, 9, 9, 9, 9]));
+        }
+        assert_eq!(logic.truncation_point(), vec![0, 0, 0, 0, 0]);
+        logic.record_watermarks(watermarks(4, vec![3, 9, 1, 9, 9]));
+        assert_eq!(logic.truncation_point(), vec![3, 4, 1, 2, 1]);
+        // Never below what is already gone
+        logic.truncated = vec![4, 0, 0, 0, 0];
+        assert_eq!(logic.truncation_point(), vec![4, 4, 1, 2, 1]);
+    }
+
+    #[test]
+    fn truncate_forgets_what_everybody_executed() {
+        let mut logic = logic();
+        for slot in 0..3 {
+            put(&mut logic, Instance::at(0, slot), State::Executed);
+        }
+        logic.truncate(&[2, 0, 0, 0, 0]).unwrap();
+        assert!(logic.get(&Instance::at(0, 1)).is_none());
+        assert_eq!(logic.state_of(&Instance::at(0, 1)), Some(State::Executed));
+        assert!(logic.get(&Instance::at(0, 2)).is_some());
+        // A late update of a dropped instance does not bring it back
+        put(&mut logic, Instance::at(0, 0), State::Committed);
+        assert!(logic.get(&Instance::at(0, 0)).is_none());
+        // Truncating less than before changes nothing
+        logic.truncate(&[1, 0, 0, 0, 0]).unwrap();
+        assert_eq!(logic.truncated, vec![2, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn restore_executes_only_what_the_snapshot_does_not_cover() {
+        let mut logic = logic();
+        let (a, b, c, d) = (
+            Instance::at(0, 0),
+            Instance::at(0, 1),
+            Instance::at(1, 0),
+            Instance::at(2, 3),
+        );
+        for instance in [a, b, c, d].iter() {
+            put(&mut logic, *instance, State::Committed);
+        }
+        logic.restore(&snapshot(vec![1, 0, 0, 0, 0], vec![c], vec![0, 0, 2, 0, 0]));
+        assert_eq!(logic.state_of(&a), Some(State::Executed));
+        assert_eq!(logic.state_of(&c), Some(State::Executed));
+        assert_eq!(logic.watermarks, vec![1, 1, 2, 0, 0]);
+        // Not covered, so it is applied again
+        let executed: Vec<Instance> = logic
+            .execute()
+            .into_iter()
+            .map(|(instance, _)| instance)
+            .collect();
+        assert_eq!(executed, vec![b, d]);
+    }
+
+    #[test]
+    fn snapshot_with_a_bad_checksum_is_rejected() {
+        let dir = env::temp_dir().join(format!("epaxos-snapshot-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("r.snapshot");
+        assert!(Snapshot::load(&path).unwrap().is_none());
+
+        let mut logic = logic();
+        put(&mut logic, Instance::at(1, 0), State::Committed);
+        let mut store = KvStore::default();
+        for (_, log_entry) in logic.execute() {
+            store.apply(&log_entry.command);
+        }
+        let snapshot = logic.snapshot(&store);
+        snapshot.save(&path).unwrap();
+        let loaded = Snapshot::load(&path).unwrap().unwrap();
+        assert!(loaded.to_grpc() == snapshot.to_grpc());
+
+        let mut bytes = fs::read(&path).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+        fs::write(&path, &bytes).unwrap();
+        let error = Snapshot::load(&path).err().unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
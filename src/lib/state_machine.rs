@@ -0,0 +1,265 @@
+use crate::command::{Command, CommandResult, Interference, Key, Replicated, Value};
+use crate::epaxos as grpc;
+use protobuf::Message;
+use std::{collections::HashMap, io, str};
+
+/// What the replicas replicate. Every replica applies the same commands in
+/// the same order, starting from the same snapshot, so `apply` must only
+/// depend on the state and the command.
+pub trait StateMachine: Send + 'static {
+    /// What clients submit and the replicas agree on
+    type Command: Replicated;
+    /// What goes back to the client of a command once it executed
+    type Output: Send + 'static;
+
+    /// Applies a committed command and returns what goes back to its client
+    fn apply(&mut self, command: &Self::Command) -> Self::Output;
+
+    /// Captures the state, for catching up replicas and truncating the log
+    fn snapshot(&self) -> Vec<u8>;
+
+    /// Replaces the state with one captured by `snapshot`. On error, the
+    /// state must be left as it was.
+    fn restore(&mut self, snapshot: &[u8]) -> io::Result<()>;
+}
+
+/// The default state machine: a map from keys to values
+#[derive(Default)]
+pub struct KvStore {
+    pub map: HashMap<Key, Value>,
+}
+
+impl StateMachine for KvStore {
+    type Command = Command;
+    type Output = CommandResult;
+
+    fn apply(&mut self, command: &Command) -> CommandResult {
+        let commands = match command {
+            Command::Multi(commands) => commands,
+            command => return self.apply_to_key(command),
+        };
+        let before: Vec<(Key, Option<Value>)> = command
+            .keys()
+            .into_iter()
+            .map(|key| (key.to_vec(), self.map.get(key).cloned()))
+            .collect();
+        let mut results: Vec<CommandResult> =
+            commands.iter().map(|command| self.apply(command)).collect();
+        let ok = results.iter().all(|result| result.ok);
+        if !ok {
+            for (key, value) in before {
+                match value {
+                    Some(value) => self.map.insert(key, value),
+                    None => self.map.remove(&key),
+                };
+            }
+            for (command, result) in commands.iter().zip(results.iter_mut()) {
+                self.observe(command, result);
+            }
+        }
+        CommandResult {
+            ok,
+            value: None,
+            results,
+        }
+    }
+
+    fn snapshot(&self) -> Vec<u8> {
+        self.to_grpc().write_to_bytes().unwrap()
+    }
+
+    fn restore(&mut self, snapshot: &[u8]) -> io::Result<()> {
+        let store = protobuf::parse_from_bytes::<grpc::KvStore>(snapshot)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        *self = KvStore::from_grpc(&store);
+        Ok(())
+    }
+}
+
+impl KvStore {
+    // Updates the values in `result` after a Multi was undone
+    fn observe(&self, command: &Command, result: &mut CommandResult) {
+        match command {
+            Command::Multi(commands) => {
+                for (command, result) in commands.iter().zip(result.results.iter_mut()) {
+                    self.observe(command, result);
+                }
+            }
+            command => result.value = self.value_after(command),
+        }
+    }
+
+    // The value a command returns. Increments commute, so replicas may apply
+    // concurrent ones in different orders and see different sums in between,
+    // and they return none.
+    fn value_after(&self, command: &Command) -> Option<Value> {
+        match command {
+            Command::Increment { .. } => None,
+            command => self.map.get(command.key()).cloned(),
+        }
+    }
+
+    fn apply_to_key(&mut self, command: &Command) -> CommandResult {
+        let store = &mut self.map;
+        let ok = match command {
+            Command::Put { key, value } => {
+                store.insert(key.clone(), value.clone());
+                true
+            }
+            Command::Delete { key } => store.remove(key).is_some(),
+            Command::Increment { key, delta } => {
+                let current = match store.get(key) {
+                    Some(value) => str::from_utf8(value)
+                        .ok()
+                        .and_then(|value| value.parse::<i64>().ok()),
+                    None => Some(0),
+                };
+                match current.map(|current| current.wrapping_add(*delta)) {
+                    Some(sum) => {
+                        store.insert(key.clone(), sum.to_string().into_bytes());
+                        true
+                    }
+                    None => false,
+                }
+            }
+            Command::Append { key, value } => {
+                store
+                    .entry(key.clone())
+                    .or_default()
+                    .extend_from_slice(value);
+                true
+            }
+            Command::Cas {
+                key,
+                expected,
+                value,
+            } => {
+                if store.get(key) == expected.as_ref() {
+                    store.insert(key.clone(), value.clone());
+                    true
+                } else {
+                    false
+                }
+            }
+            Command::Get { .. } | Command::Multi(_) => true,
+        };
+        CommandResult {
+            ok,
+            value: self.value_after(command),
+            results: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn put(key: &str, value: &str) -> Command {
+        Command::Put {
+            key: key.as_bytes().to_vec(),
+            value: value.as_bytes().to_vec(),
+        }
+    }
+
+    fn cas(key: &str, expected: &str, value: &str) -> Command {
+        Command::Cas {
+            key: key.as_bytes().to_vec(),
+            expected: Some(expected.as_bytes().to_vec()),
+            value: value.as_bytes().to_vec(),
+        }
+    }
+
+    fn store(pairs: &[(&str, &str)]) -> KvStore {
+        KvStore {
+            map: pairs
+                .iter()
+                .map(|(key, value)| (key.as_bytes().to_vec(), value.as_bytes().to_vec()))
+                .collect(),
+        }
+    }
+
+    fn values(result: &CommandResult) -> Vec<Option<Value>> {
+        result
+            .results
+            .iter()
+            .map(|result| result.value.clone())
+            .collect()
+    }
+
+    #[test]
+    fn failed_cas_undoes_the_whole_multi() {
+        let mut kv = store(&[("a", "100"), ("b", "5")]);
+        let multi = Command::Multi(vec![
+            put("b", "7"),
+            Command::Delete { key: b"a".to_vec() },
+            put("c", "1"),
+            cas("a", "100", "70"),
+        ]);
+        let result = kv.apply(&multi);
+        assert!(!result.ok);
+        assert_eq!(kv.map, store(&[("a", "100"), ("b", "5")]).map);
+        // The results show the values as they are after the undo
+        assert_eq!(
+            values(&result),
+            vec![
+                Some(b"5".to_vec()),
+                Some(b"100".to_vec()),
+                None,
+                Some(b"100".to_vec())
+            ]
+        );
+        assert_eq!(
+            result
+                .results
+                .iter()
+                .map(|result| result.ok)
+                .collect::<Vec<_>>(),
+            vec![true, true, true, false]
+        );
+    }
+
+    #[test]
+    fn nested_multi_applies_in_order() {
+        let mut kv = store(&[("a", "1")]);
+        let multi = Command::Multi(vec![
+            Command::Multi(vec![cas("a", "1", "2"), put("b", "x")]),
+            cas("a", "2", "3"),
+        ]);
+        let result = kv.apply(&multi);
+        assert!(result.ok);
+        assert_eq!(kv.map, store(&[("a", "3"), ("b", "x")]).map);
+        assert_eq!(
+            values(&result.results[0]),
+            vec![Some(b"2".to_vec()), Some(b"x".to_vec())]
+        );
+    }
+
+    #[test]
+    fn failure_after_a_nested_multi_undoes_it() {
+        let mut kv = store(&[("a", "1")]);
+        let multi = Command::Multi(vec![
+            Command::Multi(vec![put("a", "2"), put("b", "x")]),
+            cas("a", "1", "3"),
+        ]);
+        let result = kv.apply(&multi);
+        assert!(!result.ok);
+        assert!(result.results[0].ok);
+        assert_eq!(kv.map, store(&[("a", "1")]).map);
+        assert_eq!(values(&result.results[0]), vec![Some(b"1".to_vec()), None]);
+        assert_eq!(result.results[1].value, Some(b"1".to_vec()));
+    }
+
+    #[test]
+    fn increments_return_no_value() {
+        let mut kv = store(&[("a", "1")]);
+        let incr = Command::Increment {
+            key: b"a".to_vec(),
+            delta: 2,
+        };
+        assert_eq!(kv.apply(&incr).value, None);
+        let multi = Command::Multi(vec![incr.clone(), Command::Get { key: b"a".to_vec() }]);
+        assert_eq!(values(&kv.apply(&multi)), vec![None, Some(b"5".to_vec())]);
+        assert_eq!(kv.map, store(&[("a", "5")]).map);
+    }
+}
@@ -0,0 +1,233 @@
+use crate::logic::*;
+use serde::Deserialize;
+use std::{collections::HashSet, fmt, fs, io, path::Path};
+
+// Where a replica lives. Loaded from a topology file such as
+//
+//     [[replica]]
+//     id = 0
+//     host = "127.0.0.1"
+//     port = 10000
+//     region = "local"
+//     quorum = [1, 2]
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ReplicaConfig {
+    pub id: u32,
+    pub host: String,
+    pub port: u16,
+    pub region: String,
+    // Peers to try first for our quorums, e.g. the closest ones. If empty,
+    // every peer is asked at once.
+    #[serde(default)]
+    pub quorum: Vec<u32>,
+    // Not a member yet. It catches up and then asks the members to add it,
+    // see `EpaxosLogic::reconfigure`.
+    #[serde(default)]
+    pub joining: bool,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Topology {
+    // Sorted by id. Ids index the log, so removed replicas leave gaps.
+    #[serde(rename = "replica")]
+    pub replicas: Vec<ReplicaConfig>,
+}
+
+#[derive(Debug)]
+pub enum TopologyError {
+    Io(io::Error),
+    Parse(toml::de::Error),
+    Invalid(String),
+}
+
+impl Topology {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Topology, TopologyError> {
+        let text = fs::read_to_string(path).map_err(TopologyError::Io)?;
+        Topology::parse(&text)
+    }
+
+    pub fn parse(text: &str) -> Result<Topology, TopologyError> {
+        let mut topology: Topology = toml::from_str(text).map_err(TopologyError::Parse)?;
+        topology.replicas.sort_by_key(|replica| replica.id);
+        topology.validate()?;
+        Ok(topology)
+    }
+
+    // The five EC2 replicas we evaluated on
+    pub fn ec2() -> Topology {
+        let regions = [
+            "us-east-1",
+            "ap-northeast-1",
+            "us-west-1",
+            "us-west-2",
+            "eu-west-1",
+        ];
+        let replicas = REPLICA_ADDRESSES
+            .iter()
+            .zip(regions.iter())
+            .enumerate()
+            .map(|(id, (host, region))| ReplicaConfig {
+                id: id as u32,
+                host: host.to_string(),
+                port: REPLICA_PORT,
+                region: region.to_string(),
+                quorum: Vec::new(),
+                joining: false,
+            })
+            .collect();
+        Topology { replicas }
+    }
+
+    // Loads the given topology file, or falls back to the EC2 deployment
+    pub fn load_or_ec2(path: Option<&String>) -> Topology {
+        match path {
+            Some(path) => match Topology::load(path) {
+                Ok(topology) => topology,
+                Err(e) => panic!("Cannot load topology {}: {}", path, e),
+            },
+            None => Topology::ec2(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.replicas.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.replicas.is_empty()
+    }
+
+    pub fn get(&self, id: ReplicaId) -> Option<&ReplicaConfig> {
+        self.replicas.iter().find(|replica| replica.id == id.0)
+    }
+
+    pub fn contains(&self, id: ReplicaId) -> bool {
+        self.get(id).is_some()
+    }
+
+    // The replicas that are already members
+    pub fn members(&self) -> Topology {
+        Topology {
+            replicas: self
+                .replicas
+                .iter()
+                .filter(|replica| !replica.joining)
+                .cloned()
+                .collect(),
+        }
+    }
+
+    // One more than the highest id, i.e. how many rows the log needs
+    pub fn rows(&self) -> usize {
+        self.replicas
+            .iter()
+            .map(|replica| replica.id as usize + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn ids(&self) -> Vec<ReplicaId> {
+        self.replicas
+            .iter()
+            .map(|replica| ReplicaId(replica.id))
+            .collect()
+    }
+
+    fn validate(&self) -> Result<(), TopologyError> {
+        if self.replicas.is_empty() {
+            return Err(TopologyError::Invalid("no replicas".to_string()));
+        }
+        // Replica ids index the log. Removing a replica leaves a gap, but
+        // two replicas must never share an id.
+        for pair in self.replicas.windows(2) {
+            if pair[0].id == pair[1].id {
+                return Err(TopologyError::Invalid(format!(
+                    "replica id {} appears twice",
+                    pair[0].id
+                )));
+            }
+        }
+        if self.members().is_empty() {
+            return Err(TopologyError::Invalid("no members".to_string()));
+        }
+        for replica in self.replicas.iter() {
+            let mut seen = HashSet::new();
+            for peer in replica.quorum.iter() {
+                if *peer == replica.id || !self.contains(ReplicaId(*peer)) || !seen.insert(peer) {
+                    return Err(TopologyError::Invalid(format!(
+                        "replica {} has an invalid quorum peer {}",
+                        replica.id, peer
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for TopologyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TopologyError::Io(e) => write!(f, "{}", e),
+            TopologyError::Parse(e) => write!(f, "{}", e),
+            TopologyError::Invalid(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replica(id: u32, quorum: &str, joining: bool) -> String {
+        format!(
+            "[[replica]]\nid = {}\nhost = \"127.0.0.1\"\nport = {}\nregion = \"local\"\nquorum = [{}]\njoining = {}\n",
+            id,
+            10000 + id,
+            quorum,
+            joining
+        )
+    }
+
+    fn invalid(text: &str) -> String {
+        match Topology::parse(text) {
+            Err(TopologyError::Invalid(reason)) => reason,
+            Err(e) => panic!("expected an invalid topology, got {}", e),
+            Ok(_) => panic!("expected an invalid topology"),
+        }
+    }
+
+    #[test]
+    fn valid_topology_is_sorted_by_id() {
+        let text = replica(2, "0", false) + &replica(0, "2", false) + &replica(5, "", true);
+        let topology = Topology::parse(&text).unwrap();
+        assert_eq!(
+            topology.ids(),
+            vec![ReplicaId(0), ReplicaId(2), ReplicaId(5)]
+        );
+        assert_eq!(topology.members().len(), 2);
+        assert_eq!(topology.rows(), 6);
+    }
+
+    #[test]
+    fn duplicate_ids_are_rejected() {
+        let text = replica(1, "", false) + &replica(0, "", false) + &replica(1, "", false);
+        assert_eq!(invalid(&text), "replica id 1 appears twice");
+    }
+
+    #[test]
+    fn invalid_quorum_peers_are_rejected() {
+        // Itself, an unknown replica, and the same peer twice
+        for quorum in ["1, 0", "1, 7", "1, 1"].iter() {
+            let text = replica(0, quorum, false) + &replica(1, "", false);
+            assert!(invalid(&text).starts_with("replica 0 has an invalid quorum peer"));
+        }
+    }
+
+    #[test]
+    fn topology_without_members_is_rejected() {
+        assert_eq!(invalid("replica = []"), "no replicas");
+        let text = replica(0, "", true) + &replica(1, "", true);
+        assert_eq!(invalid(&text), "no members");
+    }
+}
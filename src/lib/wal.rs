@@ -0,0 +1,392 @@
+use crate::command::{Command, Replicated};
+use crate::epaxos as grpc;
+use crate::logic::*;
+use crc32fast::Hasher;
+use protobuf::Message;
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Read, Write},
+    mem,
+    path::{Path, PathBuf},
+    process,
+    sync::{Arc, Condvar, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+// Each record is framed as [length][crc32 of body][body], the first two as
+// little-endian u32 and the body as a protobuf WalRecord
+const HEADER_LEN: usize = 8;
+
+// Something an acceptor must not forget across a restart
+pub enum Record<C = Command> {
+    // An instance moved to a new state
+    Entry(Instance, LogEntry<C>),
+    // We promised to ignore ballots below this one for the instance
+    Promise(PreparePayload),
+}
+
+// Append-only, checksummed log of every change to the instance state.
+// Appends only buffer the record. A flusher thread writes out whatever has
+// piled up with a single fsync, so concurrent callers share the cost of it.
+// Handles are cheap to clone and all refer to the same log.
+#[derive(Clone)]
+pub struct Wal {
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    path: PathBuf,
+    // Only the flusher writes to it, except while the log is compacted
+    file: Mutex<File>,
+    pending: Mutex<Pending>,
+    // Tells the flusher there is something to write
+    appended: Condvar,
+    // Tells waiters that more of the log is durable
+    flushed: Condvar,
+}
+
+struct Pending {
+    buffer: Vec<u8>,
+    // Positions are counted in records. Everything up to `durable` is on
+    // disk, everything up to `appended` will be once the buffer is flushed.
+    appended: u64,
+    durable: u64,
+    // When the oldest record in the buffer was appended
+    since: Option<Instant>,
+}
+
+impl Wal {
+    /// Opens the log at `path`, creating it if needed, and returns every
+    /// record it holds. A torn or corrupt tail, left by a crash in the middle
+    /// of an append, is cut off. Buffered records wait at most `max_wait`
+    /// for others to join them before they are flushed.
+    pub fn open<C: Replicated, P: AsRef<Path>>(
+        path: P,
+        max_wait: Duration,
+    ) -> io::Result<(Wal, Vec<Record<C>>)> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(path.as_ref())?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)?;
+
+        let mut records = Vec::new();
+        let mut offset = 0;
+        while let Some((record, len)) = decode(&bytes[offset..]) {
+            records.push(record);
+            offset += len;
+        }
+        if offset < bytes.len() {
+            println!(
+                "Dropping {} bytes of torn or corrupt log after {} records",
+                bytes.len() - offset,
+                records.len()
+            );
+            file.set_len(offset as u64)?;
+            file.sync_all()?;
+        }
+
+        let wal = Wal {
+            shared: Arc::new(Shared {
+                path: path.as_ref().to_path_buf(),
+                file: Mutex::new(file),
+                pending: Mutex::new(Pending {
+                    buffer: Vec::new(),
+                    appended: 0,
+                    durable: 0,
+                    since: None,
+                }),
+                appended: Condvar::new(),
+                flushed: Condvar::new(),
+            }),
+        };
+        let shared = wal.shared.clone();
+        thread::spawn(move || flush_forever(&shared, max_wait));
+        Ok((wal, records))
+    }
+
+    /// Buffers a record and returns its position. It is durable once
+    /// `sync` returns for that position.
+    pub fn append<C: Replicated>(&self, record: &Record<C>) -> u64 {
+        let bytes = encode(record);
+        let mut pending = self.shared.pending.lock().unwrap();
+        if pending.buffer.is_empty() {
+            pending.since = Some(Instant::now());
+            self.shared.appended.notify_one();
+        }
+        pending.buffer.extend_from_slice(&bytes);
+        pending.appended += 1;
+        pending.appended
+    }
+
+    /// Position of the last record appended so far
+    pub fn appended(&self) -> u64 {
+        self.shared.pending.lock().unwrap().appended
+    }
+
+    /// Blocks until every record up to `position` is on disk
+    pub fn sync(&self, position: u64) {
+        let mut pending = self.shared.pending.lock().unwrap();
+        while pending.durable < position {
+            pending = self.shared.flushed.wait(pending).unwrap();
+        }
+    }
+
+    /// Atomically replaces the whole log with `records`. The caller must
+    /// make sure nothing is appended until this returns.
+    pub fn compact<C: Replicated>(&self, records: &[Record<C>]) -> io::Result<()> {
+        self.sync(self.appended());
+        let mut file = self.shared.file.lock().unwrap();
+        let path = &self.shared.path;
+        let compacted = path.with_extension("compacted");
+        let mut new_file = File::create(&compacted)?;
+        for record in records {
+            new_file.write_all(&encode(record))?;
+        }
+        new_file.sync_all()?;
+        fs::rename(&compacted, path)?;
+        // From here on the old log is gone, so we cannot back out
+        match sync_dir(path).and_then(|_| OpenOptions::new().append(true).open(path)) {
+            Ok(new_file) => *file = new_file,
+            Err(e) => {
+                eprintln!("Cannot reopen the compacted write-ahead log: {}", e);
+                process::abort();
+            }
+        }
+        Ok(())
+    }
+}
+
+fn flush_forever(shared: &Shared, max_wait: Duration) {
+    loop {
+        let (buffer, position) = {
+            let mut pending = shared.pending.lock().unwrap();
+            while pending.buffer.is_empty() {
+                pending = shared.appended.wait(pending).unwrap();
+            }
+            // Give concurrent updates a chance to join this flush
+            let deadline = pending.since.unwrap() + max_wait;
+            let mut now = Instant::now();
+            while now < deadline {
+                pending = shared
+                    .appended
+                    .wait_timeout(pending, deadline - now)
+                    .unwrap()
+                    .0;
+                now = Instant::now();
+            }
+            pending.since = None;
+            (mem::take(&mut pending.buffer), pending.appended)
+        };
+        let mut file = shared.file.lock().unwrap();
+        if let Err(e) = file.write_all(&buffer).and_then(|_| file.sync_data()) {
+            // An acceptor that cannot remember what it promised must stop
+            eprintln!("Cannot write to the write-ahead log: {}", e);
+            process::abort();
+        }
+        drop(file);
+        shared.pending.lock().unwrap().durable = position;
+        shared.flushed.notify_all();
+    }
+}
+
+// Makes the creation or renaming of `path` durable
+pub(crate) fn sync_dir(path: &Path) -> io::Result<()> {
+    let dir = match path.parent() {
+        Some(dir) if dir != Path::new("") => dir,
+        _ => Path::new("."),
+    };
+    File::open(dir)?.sync_all()
+}
+
+pub(crate) fn checksum(body: &[u8]) -> u32 {
+    let mut hasher = Hasher::new();
+    hasher.update(body);
+    hasher.finalize()
+}
+
+fn encode<C: Replicated>(record: &Record<C>) -> Vec<u8> {
+    let body = record.to_grpc().write_to_bytes().unwrap();
+    let mut bytes = Vec::with_capacity(HEADER_LEN + body.len());
+    bytes.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&checksum(&body).to_le_bytes());
+    bytes.extend_from_slice(&body);
+    bytes
+}
+
+// Decodes the record at the start of `bytes`, with the number of bytes it
+// takes up. None if it is incomplete or does not match its checksum.
+fn decode<C: Replicated>(bytes: &[u8]) -> Option<(Record<C>, usize)> {
+    if bytes.len() < HEADER_LEN {
+        return None;
+    }
+    let mut word = [0; 4];
+    word.copy_from_slice(&bytes[0..4]);
+    let len = u32::from_le_bytes(word) as usize;
+    word.copy_from_slice(&bytes[4..8]);
+    let crc = u32::from_le_bytes(word);
+    let body = bytes.get(HEADER_LEN..HEADER_LEN + len)?;
+    if checksum(body) != crc {
+        return None;
+    }
+    let record = protobuf::parse_from_bytes::<grpc::WalRecord>(body).ok()?;
+    Some((Record::from_grpc(&record), HEADER_LEN + len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::Command;
+    use crate::topology::Topology;
+    use std::env;
+
+    // A fresh directory per test, so they can run in parallel
+    fn dir(test: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("epaxos-wal-{}-{}", process::id(), test));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn entry(slot: u32, state: State) -> Record {
+        let log_entry = LogEntry {
+            command: Command::Put {
+                key: format!("key{}", slot).into_bytes(),
+                value: b"value".to_vec(),
+            },
+            seq: slot + 1,
+            deps: vec![Instance::at(1, slot)],
+            state,
+            noop: false,
+            ballot: Ballot::default(),
+            epoch: 0,
+            reconfig: None,
+        };
+        Record::Entry(Instance::at(0, slot), log_entry)
+    }
+
+    fn promise(slot: u32) -> Record {
+        Record::Promise(PreparePayload {
+            ballot: Ballot {
+                epoch: 0,
+                number: 2,
+                replica: 1,
+            },
+            instance: Instance::at(0, slot),
+        })
+    }
+
+    fn write(path: &Path, records: &[Record]) {
+        let (wal, replayed) = Wal::open::<Command, _>(path, Duration::from_millis(0)).unwrap();
+        assert!(replayed.is_empty());
+        let mut position = 0;
+        for record in records {
+            position = wal.append(record);
+        }
+        wal.sync(position);
+    }
+
+    fn same(a: &[Record], b: &[Record]) -> bool {
+        a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.to_grpc() == b.to_grpc())
+    }
+
+    #[test]
+    fn records_survive_a_reopen() {
+        let path = dir("round-trip").join("r.wal");
+        let records = vec![
+            entry(0, State::PreAccepted),
+            promise(1),
+            entry(0, State::Committed),
+        ];
+        write(&path, &records);
+        let (_, replayed) = Wal::open(&path, Duration::from_millis(0)).unwrap();
+        assert!(same(&replayed, &records));
+    }
+
+    #[test]
+    fn torn_tail_is_cut_off() {
+        let path = dir("torn").join("r.wal");
+        let records = vec![entry(0, State::PreAccepted), entry(1, State::PreAccepted)];
+        write(&path, &records);
+        // A crash in the middle of writing the second record
+        let len = fs::metadata(&path).unwrap().len();
+        let first = encode(&records[0]).len() as u64;
+        OpenOptions::new()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_len(len - 3)
+            .unwrap();
+
+        let (wal, replayed) = Wal::open(&path, Duration::from_millis(0)).unwrap();
+        assert!(same(&replayed, &records[..1]));
+        assert_eq!(fs::metadata(&path).unwrap().len(), first);
+        // What comes next goes right after the last good record
+        wal.sync(wal.append(&promise(2)));
+        let (_, replayed) = Wal::open(&path, Duration::from_millis(0)).unwrap();
+        assert!(same(&replayed, &[entry(0, State::PreAccepted), promise(2)]));
+    }
+
+    #[test]
+    fn corrupt_tail_is_cut_off() {
+        let path = dir("corrupt").join("r.wal");
+        let records = vec![promise(0), entry(1, State::Accepted)];
+        write(&path, &records);
+        let mut bytes = fs::read(&path).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xff;
+        fs::write(&path, &bytes).unwrap();
+
+        let (_, replayed) = Wal::open(&path, Duration::from_millis(0)).unwrap();
+        assert!(same(&replayed, &records[..1]));
+    }
+
+    #[test]
+    fn replay_restores_the_log_and_promises() {
+        let path = dir("replay").join("r.wal");
+        let topology = Topology::ec2();
+        {
+            let mut logic =
+                EpaxosLogic::open(ReplicaId(0), &topology, &path, Duration::from_millis(0))
+                    .unwrap();
+            for slot in 0..3 {
+                if let Record::Entry(instance, log_entry) = entry(slot, State::PreAccepted) {
+                    logic.update_log(log_entry, &instance);
+                }
+            }
+            if let Record::Entry(instance, log_entry) = entry(1, State::Committed) {
+                logic.update_log(log_entry, &instance);
+            }
+            logic.prepare_(Prepare(PreparePayload {
+                ballot: Ballot {
+                    epoch: 0,
+                    number: 1,
+                    replica: 2,
+                },
+                instance: Instance::at(2, 5),
+            }));
+            let wal = logic.wal().unwrap();
+            wal.sync(wal.appended());
+        }
+
+        let logic: EpaxosLogic =
+            EpaxosLogic::open(ReplicaId(0), &topology, &path, Duration::from_millis(0)).unwrap();
+        assert_eq!(logic.cmds[0].len(), 3);
+        assert_eq!(logic.instance_number, 3);
+        assert!(logic.state_of(&Instance::at(0, 1)) == Some(State::Committed));
+        assert!(logic.state_of(&Instance::at(0, 2)) == Some(State::PreAccepted));
+        assert!(logic.unexecuted.contains(&Instance::at(0, 1)));
+        assert_eq!(logic.get(&Instance::at(0, 2)).unwrap().seq, 3);
+        assert_eq!(
+            logic.promised(&Instance::at(2, 5)),
+            Ballot {
+                epoch: 0,
+                number: 1,
+                replica: 2,
+            }
+        );
+    }
+}